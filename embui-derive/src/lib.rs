@@ -0,0 +1,319 @@
+//! Derive macros for embui widgets.
+//!
+//! `#[derive(Widget)]` writes the boilerplate every widget repeats, so a
+//! custom widget only needs its fields, a constructor and
+//! `ThemedWidget::draw_with_theme`:
+//!
+//! ```
+//! use core::marker::PhantomData;
+//!
+//! use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+//! use embui::{StateManager, Theme, ThemedWidget, Widget, widgets::Frame};
+//!
+//! #[derive(Clone, Widget)]
+//! struct Swatch<M: Copy, C: PixelColor> {
+//!     ph: PhantomData<M>,
+//!     color: C,
+//!     #[bounds]
+//!     bounds: Rectangle,
+//!     #[state]
+//!     state_manager: StateManager,
+//! }
+//!
+//! impl<M, D, T, C> ThemedWidget<D, T, C> for Swatch<M, C>
+//! where
+//!     M: Copy,
+//!     D: DrawTarget<Color = C>,
+//!     T: Theme<C>,
+//!     C: PixelColor + Default + From<Rgb888>,
+//! {
+//!     fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), D::Error> {
+//!         let state = self.get_state();
+//!         Frame::themed(self.bounds, theme, state, self.color, theme.border_color()).draw(target)
+//!     }
+//! }
+//! # let swatch: Swatch<(), Rgb888> = Swatch {
+//! #     ph: PhantomData,
+//! #     color: Rgb888::RED,
+//! #     bounds: Rectangle::new(Point::zero(), Size::new(10, 10)),
+//! #     state_manager: StateManager::new(),
+//! # };
+//! # assert_eq!(swatch.translate(Point::new(5, 0)).bounding_box().top_left, Point::new(5, 0));
+//! ```
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Error, Fields, GenericParam, Ident, Member, Type, parse_macro_input,
+    parse_quote,
+};
+
+/// Implement `Widget`, `Transform`, `Dimensions`, `layout::Place` and
+/// `screen::Element` for a widget struct.
+///
+/// Field attributes:
+///
+/// - `#[state]` on the `StateManager` (required)
+/// - `#[bounds]` on a `Rectangle`, or `#[position]` on a `Point` together
+///   with `#[size]` on a `Size` (required)
+/// - `#[binding]` on fields with `is_dirty` and `mark_clean` methods, such
+///   as `binding::Bound`, so changes to them redraw the widget
+/// - `#[message]` on an `Option<M>` sent while the widget is pressed
+///
+/// The message type is the generic parameter `M`; other widgets name it
+/// with `#[widget(message = MyMessage)]`. `Transform` needs the widget to be
+/// `Clone`.
+#[proc_macro_derive(
+    Widget,
+    attributes(widget, state, bounds, position, size, binding, message)
+)]
+pub fn derive_widget(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Where a widget keeps its position and size
+enum Bounds {
+    Rectangle(Member),
+    Parts { position: Member, size: Member },
+}
+
+struct WidgetFields {
+    state: Member,
+    bounds: Bounds,
+    bindings: Vec<Member>,
+    message: Option<Member>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let message = message_type(&input)?;
+    let fields = widget_fields(&input)?;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let state = &fields.state;
+    let to_message = match &fields.message {
+        Some(field) => quote! {
+            match ::embui::Widget::get_state(self) {
+                ::embui::WidgetState::Pressed => self.#field,
+                _ => None,
+            }
+        },
+        None => quote! { None },
+    };
+    let dirty = if fields.bindings.is_empty() {
+        quote! {}
+    } else {
+        let bindings = &fields.bindings;
+        quote! {
+            fn is_dirty(&self) -> bool {
+                self.#state.is_dirty() #(|| self.#bindings.is_dirty())*
+            }
+            fn mark_clean(&mut self) {
+                self.#state.clear_dirty();
+                #(self.#bindings.mark_clean();)*
+            }
+        }
+    };
+
+    let (bounding_box, translate, place) = match &fields.bounds {
+        Bounds::Rectangle(bounds) => (
+            quote! { self.#bounds },
+            quote! { self.#bounds.top_left += by; },
+            quote! { self.#bounds = bounds; },
+        ),
+        Bounds::Parts { position, size } => (
+            quote! { ::embui::__private::Rectangle::new(self.#position, self.#size) },
+            quote! { self.#position += by; },
+            quote! {
+                self.#position = bounds.top_left;
+                self.#size = bounds.size;
+            },
+        ),
+    };
+
+    // `Element` draws with any colour the widget has a `ThemedWidget` impl
+    // for, so it gets its own parameters next to the widget's
+    let mut element_generics = input.generics.clone();
+    element_generics
+        .params
+        .extend([parse_quote!(__D), parse_quote!(__T), parse_quote!(__C)] as [GenericParam; 3]);
+    let element_where = element_generics.make_where_clause();
+    element_where.predicates.extend([
+        parse_quote!(__D: ::embui::__private::DrawTarget<Color = __C>),
+        parse_quote!(__T: ::embui::Theme<__C>),
+        parse_quote!(
+            __C: ::embui::__private::PixelColor + Default + From<::embui::__private::Rgb888>
+        ),
+        parse_quote!(Self: ::embui::ThemedWidget<__D, __T, __C>),
+    ] as [syn::WherePredicate; 4]);
+    let (element_impl_generics, _, element_where_clause) = element_generics.split_for_impl();
+
+    let mut transform_generics = input.generics.clone();
+    transform_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(Self: Clone));
+    let (_, _, transform_where_clause) = transform_generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::embui::Widget<#message> for #name #ty_generics #where_clause {
+            fn to_message(&self) -> Option<#message> {
+                #to_message
+            }
+            fn get_state_manager(&self) -> &::embui::StateManager {
+                &self.#state
+            }
+            fn get_state_manager_mut(&mut self) -> &mut ::embui::StateManager {
+                &mut self.#state
+            }
+            #dirty
+        }
+
+        impl #element_impl_generics ::embui::screen::Element<#message, __D, __T, __C>
+            for #name #ty_generics #element_where_clause
+        {
+        }
+
+        impl #impl_generics ::embui::__private::Transform for #name #ty_generics
+            #transform_where_clause
+        {
+            fn translate(&self, by: ::embui::__private::Point) -> Self {
+                let mut moved = self.clone();
+                ::embui::__private::Transform::translate_mut(&mut moved, by);
+                moved
+            }
+            fn translate_mut(&mut self, by: ::embui::__private::Point) -> &mut Self {
+                #translate
+                self
+            }
+        }
+
+        impl #impl_generics ::embui::layout::Place for #name #ty_generics #where_clause {
+            fn place(&mut self, bounds: ::embui::__private::Rectangle) {
+                #place
+                self.#state.mark_dirty();
+            }
+        }
+
+        impl #impl_generics ::embui::__private::Dimensions for #name #ty_generics #where_clause {
+            fn bounding_box(&self) -> ::embui::__private::Rectangle {
+                #bounding_box
+            }
+        }
+    })
+}
+
+/// The type given by `#[widget(message = ..)]`, or the parameter `M`
+fn message_type(input: &DeriveInput) -> syn::Result<Type> {
+    let mut message = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("widget"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("message") {
+                message = Some(meta.value()?.parse::<Type>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `message = Type`"))
+            }
+        })?;
+    }
+    if let Some(message) = message {
+        return Ok(message);
+    }
+    let m = Ident::new("M", proc_macro2::Span::call_site());
+    if input.generics.type_params().any(|param| param.ident == m) {
+        Ok(parse_quote!(#m))
+    } else {
+        Err(Error::new_spanned(
+            &input.ident,
+            "no message parameter `M`, add `#[widget(message = Type)]`",
+        ))
+    }
+}
+
+fn widget_fields(input: &DeriveInput) -> syn::Result<WidgetFields> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`Widget` can only be derived for structs",
+        ));
+    };
+    let fields = match &data.fields {
+        Fields::Named(fields) => &fields.named,
+        Fields::Unnamed(fields) => &fields.unnamed,
+        Fields::Unit => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "a widget needs `#[state]` and `#[bounds]` fields",
+            ));
+        }
+    };
+
+    let mut state = None;
+    let mut rectangle = None;
+    let mut position = None;
+    let mut size = None;
+    let mut bindings = Vec::new();
+    let mut message = None;
+    for (index, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        for attr in &field.attrs {
+            let slot = if attr.path().is_ident("state") {
+                &mut state
+            } else if attr.path().is_ident("bounds") {
+                &mut rectangle
+            } else if attr.path().is_ident("position") {
+                &mut position
+            } else if attr.path().is_ident("size") {
+                &mut size
+            } else if attr.path().is_ident("message") {
+                &mut message
+            } else if attr.path().is_ident("binding") {
+                bindings.push(member.clone());
+                continue;
+            } else {
+                continue;
+            };
+            if slot.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "attribute used on more than one field",
+                ));
+            }
+            *slot = Some(member.clone());
+        }
+    }
+
+    let state = state.ok_or_else(|| {
+        Error::new_spanned(
+            &input.ident,
+            "missing `#[state]` on the `StateManager` field",
+        )
+    })?;
+    let bounds = match (rectangle, position, size) {
+        (Some(rectangle), None, None) => Bounds::Rectangle(rectangle),
+        (None, Some(position), Some(size)) => Bounds::Parts { position, size },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "expected either `#[bounds]` or both `#[position]` and `#[size]`",
+            ));
+        }
+    };
+    Ok(WidgetFields {
+        state,
+        bounds,
+        bindings,
+        message,
+    })
+}
@@ -0,0 +1,49 @@
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*};
+use embedded_graphics_simulator::{
+    OutputSettingsBuilder, SimulatorDisplay, SimulatorEvent, Window,
+};
+use embui::{
+    ThemedWidget,
+    themes::DefaultTheme,
+    widgets::{BarChart, LineChart, ScrollMode, Thresholds, YRange},
+};
+
+fn main() -> Result<(), core::convert::Infallible> {
+    let mut display: SimulatorDisplay<Rgb888> = SimulatorDisplay::new(Size::new(320, 240));
+    let output_settings = OutputSettingsBuilder::new().scale(2).build();
+    let mut window = Window::new("chart.rs", &output_settings);
+
+    let theme = DefaultTheme::<Rgb888>::new();
+    #[derive(Clone, Copy)]
+    enum Message {}
+
+    let mut temperatures: LineChart<Message, Rgb888, 64, 2> =
+        LineChart::new(Point::new(8, 8), Size::new(304, 120))
+            .with_grid(4, 8)
+            .with_mode(ScrollMode::Sweep)
+            .with_range(YRange::Fixed { min: -20, max: 60 });
+    let mut load: BarChart<Message, Rgb888, 16> =
+        BarChart::new(Point::new(8, 136), Size::new(304, 96))
+            .with_range(YRange::Fixed { min: 0, max: 100 })
+            .with_thresholds(Thresholds::new(70, 90));
+
+    temperatures.draw_with_theme(&mut display, &theme)?;
+    let mut t: i32 = 0;
+    'running: loop {
+        t += 1;
+        let inside = 20 + (t % 40 - 20).abs() / 2;
+        let outside = (t % 80 - 40).abs() - 10;
+        temperatures.push([inside, outside]);
+        load.push((t * 37) % 100);
+
+        temperatures.draw_latest(&mut display, &theme)?;
+        load.draw_with_theme(&mut display, &theme)?;
+        window.update(&display);
+
+        if window.events().any(|e| matches!(e, SimulatorEvent::Quit)) {
+            break 'running;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    Ok(())
+}
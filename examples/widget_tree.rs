@@ -5,9 +5,7 @@ use embedded_graphics_simulator::{
 };
 use embui::widgets::Button;
 use embui::{
-    InputEvent, Response, Widget,
-    screen::{Draw, Element},
-    themes::DefaultTheme,
+    Application, Command, InputEvent, Runtime, Signal, screen::Element, themes::DefaultTheme,
     widgets::Number,
 };
 
@@ -22,43 +20,50 @@ fn main() -> Result<(), core::convert::Infallible> {
         Increment,
         Decrement,
     }
-    struct Model<'a> {
-        counter: Number<Message>,
-        inc_button: Button<'a, Message, Rgb888>,
-        dec_button: Button<'a, Message, Rgb888>,
+    /// The model owns the counter, the widgets only display it
+    struct Model {
+        counter: Signal<i32>,
     }
-    impl Model<'_> {
-        fn handle_event(&mut self, event: InputEvent) {
-            if let Response::Changed(Some(msg)) = self.inc_button.handle_event(event) {
-                self.update(msg)
-            }
-            if let Response::Changed(Some(msg)) = self.counter.handle_event(event) {
-                self.update(msg)
-            }
-            if let Response::Changed(Some(msg)) = self.dec_button.handle_event(event) {
-                self.update(msg)
-            }
-        }
-        fn update(&mut self, msg: Message) {
+    impl Model {
+        fn update(&self, msg: Message) {
             match msg {
-                Message::Increment => {
-                    self.counter.set(self.counter.get() + 1);
-                }
-                Message::Decrement => {
-                    self.counter.set(self.counter.get() - 1);
-                }
+                Message::Increment => self.counter.update(|n| n + 1),
+                Message::Decrement => self.counter.update(|n| n - 1),
             }
         }
+    }
+    type Display = SimulatorDisplay<Rgb888>;
+    struct Ui<'a> {
+        model: &'a Model,
+        counter: Number<'a, Message>,
+        inc_button: Button<'a, Message, Rgb888>,
+        dec_button: Button<'a, Message, Rgb888>,
+    }
+    impl Application<Display, DefaultTheme<Rgb888>, Rgb888> for Ui<'_> {
+        type Message = Message;
+
+        fn update(&mut self, msg: Message) -> Command<Message> {
+            self.model.update(msg);
+            Command::None
+        }
+        #[allow(refining_impl_trait)]
         fn view(
-            &self,
-        ) -> [&dyn Element<Message, SimulatorDisplay<Rgb888>, DefaultTheme<Rgb888>, Rgb888>; 3]
-        {
-            [&self.inc_button, &self.counter, &self.dec_button]
+            &mut self,
+        ) -> [&mut dyn Element<Message, Display, DefaultTheme<Rgb888>, Rgb888>; 3] {
+            [
+                &mut self.inc_button,
+                &mut self.counter,
+                &mut self.dec_button,
+            ]
         }
     }
 
-    let mut model = Model {
-        counter: Number::new(Point::new(0, 64), Size::new(64, 64)),
+    let model = Model {
+        counter: Signal::new(0),
+    };
+    let mut runtime: Runtime<_, _, _, _> = Runtime::new(Ui {
+        model: &model,
+        counter: Number::new(Point::new(0, 64), Size::new(64, 64)).bind(&model.counter),
         inc_button: Button::new("+")
             .on_press(Message::Increment)
             .with_position(Point::new(0, 0))
@@ -67,10 +72,12 @@ fn main() -> Result<(), core::convert::Infallible> {
             .on_press(Message::Decrement)
             .with_position(Point::new(0, 128))
             .with_size(Size::new(32, 32)),
-    };
+    });
 
+    let start = std::time::Instant::now();
     window.update(&display);
     'running: loop {
+        runtime.tick(start.elapsed().as_millis() as u64);
         for event in window.events() {
             if let Some(ev) = match event {
                 embedded_graphics_simulator::SimulatorEvent::KeyUp { .. } => None,
@@ -106,10 +113,11 @@ fn main() -> Result<(), core::convert::Infallible> {
                     break 'running;
                 }
             } {
-                model.handle_event(ev);
+                runtime.handle_event(ev);
             }
         }
-        model.view().iter().draw_all(&mut display, &theme)?;
+        // Only redraw what changed since the last frame
+        runtime.draw(&mut display, &theme)?;
         window.update(&display);
     }
     Ok(())
@@ -0,0 +1,1040 @@
+//! Elm-style applications.
+//!
+//! An [`Application`] owns its state and its widgets. The [`Runtime`] feeds
+//! it input, queues the messages its widgets produce, hands them to
+//! [`Application::update`] one at a time and redraws whatever changed.
+//! Side effects, like timers or moving the focus, are returned from `update`
+//! as [`Command`]s and carried out by the runtime.
+use core::marker::PhantomData;
+
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+use heapless::{Deque, Vec};
+
+use crate::{
+    InputEvent, Theme, Widget, WidgetId,
+    i18n::Direction,
+    router::{self, Router, TouchId, TouchPhase},
+    screen::Element,
+    timer::{Blink, IdleTimeout, Timers},
+};
+
+/// A side effect requested by [`Application::update`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Command<M> {
+    #[default]
+    None,
+    /// Queue another message
+    Message(M),
+    /// Send a message once, after this many milliseconds
+    Delay(u32, M),
+    /// Send a message every this many milliseconds
+    Every(u32, M),
+    /// Focus the widget at this index in [`Application::view`]
+    Focus(usize),
+    /// Focus the widget with this id
+    FocusWidget(WidgetId),
+    /// Move the focus to the next widget that can take it
+    FocusNext,
+    /// Move the focus to the previous widget that can take it
+    FocusPrevious,
+    /// Enable or disable the widget with this id
+    SetEnabled(WidgetId, bool),
+    /// Scroll the widget with this id on screen, see
+    /// [`Application::scroll_into_view`]
+    ScrollIntoView(WidgetId),
+    /// Switch to another screen, see [`Application::navigate`]
+    Navigate(usize),
+    /// Hand an input event to the focused widget, e.g. a key typed on an
+    /// [`OnScreenKeyboard`](crate::widgets::OnScreenKeyboard)
+    Input(InputEvent),
+}
+
+/// A model, the messages that change it and the widgets that show it.
+///
+/// The display, theme and message types must not borrow anything, so that
+/// `view` can hand out trait objects tied only to the application.
+///
+/// ```ignore
+/// impl Application<Display, DefaultTheme<Rgb888>, Rgb888> for Counter<'_> {
+///     type Message = Message;
+///
+///     fn update(&mut self, message: Message) -> Command<Message> {
+///         match message {
+///             Message::Increment => self.count.set(self.count.get() + 1),
+///             Message::Decrement => self.count.set(self.count.get() - 1),
+///             // Count up on its own, once a second
+///             Message::Start => return Command::Every(1000, Message::Increment),
+///         }
+///         Command::None
+///     }
+///     // Returning the array type itself lets the widgets coerce to `dyn Element`
+///     #[allow(refining_impl_trait)]
+///     fn view(&mut self) -> [&mut dyn Element<Message, Display, DefaultTheme<Rgb888>, Rgb888>; 3] {
+///         [&mut self.increment, &mut self.count, &mut self.decrement]
+///     }
+/// }
+/// ```
+pub trait Application<D, T, C>
+where
+    D: DrawTarget<Color = C> + 'static,
+    T: Theme<C> + 'static,
+    C: PixelColor + Default + From<Rgb888> + 'static,
+{
+    type Message: Copy + 'static;
+
+    /// Apply a message to the model
+    fn update(&mut self, message: Self::Message) -> Command<Self::Message>;
+
+    /// Apply a message from the widget with id `sender`, or from outside
+    /// the view when `None`. Override instead of `update` when several
+    /// widgets send the same message, e.g. the rows of a list.
+    fn update_from(
+        &mut self,
+        message: Self::Message,
+        _sender: Option<WidgetId>,
+    ) -> Command<Self::Message> {
+        self.update(message)
+    }
+
+    /// The widgets to draw and dispatch input to, in drawing order.
+    ///
+    /// Takes `&mut self` because widgets keep their own hover and press
+    /// state, which the runtime updates while dispatching.
+    fn view(&mut self) -> impl AsMut<[&mut dyn Element<Self::Message, D, T, C>]>;
+
+    /// Switch `view` to another screen. Called for [`Command::Navigate`];
+    /// applications with a single screen can ignore it.
+    fn navigate(&mut self, _screen: usize) {}
+
+    /// Lay out the view again after the locale changed, e.g. mirror it
+    /// with [`Direction::mirror`] for right-to-left text. Called by
+    /// [`Runtime::locale_changed`].
+    fn relayout(&mut self, _direction: Direction) {}
+
+    /// Scroll so that `bounds`, the current bounds of a widget in the view,
+    /// is visible. Called for [`Command::ScrollIntoView`]; applications
+    /// without scrolling containers can ignore it.
+    fn scroll_into_view(&mut self, _bounds: Rectangle) {}
+}
+
+/// Runs an [`Application`]: event dispatch, the message queue and redraws.
+///
+/// Messages produced while handling one event are queued, up to `Q` of
+/// them, and applied once every widget has seen the event. Messages that do
+/// not fit are dropped. Up to `Q` delayed and periodic messages can be
+/// pending at once.
+///
+/// Time is whatever monotonic millisecond count is passed to
+/// [`Runtime::tick`], so timers run the same on hardware and in tests.
+pub struct Runtime<A, D, T, C, const Q: usize = 8>
+where
+    A: Application<D, T, C>,
+    D: DrawTarget<Color = C> + 'static,
+    T: Theme<C> + 'static,
+    C: PixelColor + Default + From<Rgb888> + 'static,
+{
+    ph: PhantomData<fn(&mut D, &T)>,
+    app: A,
+    queue: Deque<(A::Message, Option<WidgetId>), Q>,
+    timers: Timers<A::Message, Q>,
+    idle: Option<IdleTimeout<A::Message>>,
+    blink: Option<Blink>,
+    now: u64,
+    focused: Option<usize>,
+    router: Router,
+    overlays: Vec<Rectangle, 4>,
+    redraw: bool,
+    min_touch_target: Option<Size>,
+    small_touch_targets: Vec<usize, 8>,
+    small_touch_target_count: usize,
+}
+
+impl<A, D, T, C, const Q: usize> Runtime<A, D, T, C, Q>
+where
+    A: Application<D, T, C>,
+    D: DrawTarget<Color = C> + 'static,
+    T: Theme<C> + 'static,
+    C: PixelColor + Default + From<Rgb888> + 'static,
+{
+    pub fn new(app: A) -> Self {
+        Self {
+            ph: PhantomData,
+            app,
+            queue: Deque::new(),
+            timers: Timers::new(),
+            idle: None,
+            blink: None,
+            now: 0,
+            focused: None,
+            router: Router::new(),
+            overlays: Vec::new(),
+            redraw: true,
+            min_touch_target: None,
+            small_touch_targets: Vec::new(),
+            small_touch_target_count: 0,
+        }
+    }
+    /// Send `message` after `timeout` milliseconds without input
+    pub fn with_idle_timeout(mut self, timeout: u32, message: A::Message) -> Self {
+        self.idle = Some(IdleTimeout::new(timeout, message));
+        self
+    }
+    /// Blink the text cursor of the focused widget every `period`
+    /// milliseconds, see [`StateManager::cursor_visible`](crate::StateManager::cursor_visible)
+    pub fn with_cursor_blink(mut self, period: u32) -> Self {
+        self.blink = Some(Blink::new(period));
+        self
+    }
+
+    /// In debug builds, check that interactive widgets are at least `size`,
+    /// see [`touch_target_size`](crate::themes::touch_target_size).
+    /// Offenders are listed by [`Runtime::small_touch_targets`].
+    pub fn with_min_touch_target(mut self, size: Size) -> Self {
+        self.min_touch_target = Some(size);
+        self
+    }
+
+    pub fn app(&self) -> &A {
+        &self.app
+    }
+    pub fn app_mut(&mut self) -> &mut A {
+        &mut self.app
+    }
+    pub fn into_app(self) -> A {
+        self.app
+    }
+    /// The time of the last [`Runtime::tick`]
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+    /// Index of the focused widget in the current view
+    pub fn focused(&self) -> Option<usize> {
+        self.focused
+    }
+    /// Indices of the first 8 interactive widgets in the current view that
+    /// are smaller than the minimum touch target. Only checked in debug
+    /// builds, when the whole view is drawn.
+    pub fn small_touch_targets(&self) -> &[usize] {
+        &self.small_touch_targets
+    }
+    /// How many widgets are too small, including those beyond the first 8
+    /// in [`Runtime::small_touch_targets`]
+    pub fn small_touch_target_count(&self) -> usize {
+        self.small_touch_target_count
+    }
+    /// When [`Runtime::tick`] has something to do next: a timer, the idle
+    /// timeout or a cursor blink. `None` means it can wait for input.
+    pub fn next_deadline(&self) -> Option<u64> {
+        let timer = self.timers.next_due();
+        let idle = self.idle.as_ref().and_then(IdleTimeout::deadline);
+        let blink = self
+            .blink
+            .filter(|_| self.focused.is_some())
+            .map(|blink| blink.next_toggle(self.now));
+        [timer, idle, blink].into_iter().flatten().min()
+    }
+    /// Timers started with [`Command::Delay`] and [`Command::Every`]. Add
+    /// timers here directly to get a [`TimerId`](crate::timer::TimerId)
+    /// that can cancel them.
+    pub fn timers_mut(&mut self) -> &mut Timers<A::Message, Q> {
+        &mut self.timers
+    }
+
+    /// Queue a message from outside the widget tree, e.g. a timer or a
+    /// sensor reading. Gives the message back if the queue is full.
+    pub fn send(&mut self, message: A::Message) -> Result<(), A::Message> {
+        self.queue
+            .push_back((message, None))
+            .map_err(|(message, _)| message)
+    }
+
+    /// Dispatch `event` to the widgets it is meant for, see
+    /// [`router`], then apply the messages they produced.
+    /// Key presses go to the focused widget, like [`Runtime::input`], unless
+    /// a popup is open. Returns true if any message was applied.
+    pub fn handle_event(&mut self, event: InputEvent) -> bool {
+        if let Some(idle) = &mut self.idle {
+            idle.activity(self.now);
+        }
+        self.drop_lost_focus();
+        if matches!(event, InputEvent::KeyPress(_) | InputEvent::Key(_)) {
+            if router::popup(self.app.view().as_mut()).is_none() {
+                self.input(event);
+                return self.update();
+            }
+            self.restart_blink();
+        }
+        let queue = &mut self.queue;
+        self.router
+            .route(self.app.view().as_mut(), event, |message, sender| {
+                let _ = queue.push_back((message, sender));
+            });
+        self.update()
+    }
+
+    /// Dispatch a finger of a multi-touch panel, like
+    /// [`Runtime::handle_event`]
+    pub fn handle_touch(&mut self, finger: TouchId, phase: TouchPhase, point: Point) -> bool {
+        if let Some(idle) = &mut self.idle {
+            idle.activity(self.now);
+        }
+        self.drop_lost_focus();
+        let queue = &mut self.queue;
+        self.router.touch(
+            self.app.view().as_mut(),
+            finger,
+            phase,
+            point,
+            |message, sender| {
+                let _ = queue.push_back((message, sender));
+            },
+        );
+        self.update()
+    }
+
+    /// Advance the clock to `now` milliseconds, send every message that is
+    /// due and apply them. Returns true if any message was applied.
+    pub fn tick(&mut self, now: u64) -> bool {
+        self.now = now;
+        self.timers.poll(now, |message| {
+            let _ = self.queue.push_back((message, None));
+        });
+        if let Some(message) = self.idle.as_mut().and_then(|idle| idle.poll(now)) {
+            let _ = self.queue.push_back((message, None));
+        }
+        if let Some(blink) = &mut self.blink
+            && blink.update(now)
+        {
+            let visible = blink.is_visible();
+            self.set_cursor_visible(visible);
+        }
+        self.update()
+    }
+
+    /// Apply all queued messages. Returns true if there were any.
+    pub fn update(&mut self) -> bool {
+        let mut updated = false;
+        while let Some((message, sender)) = self.queue.pop_front() {
+            let command = self.app.update_from(message, sender);
+            self.run(command);
+            updated = true;
+        }
+        self.drop_lost_focus();
+        updated
+    }
+
+    fn run(&mut self, command: Command<A::Message>) {
+        match command {
+            Command::None => {}
+            Command::Message(message) => {
+                let _ = self.queue.push_back((message, None));
+            }
+            Command::Delay(ms, message) => {
+                self.timers.after(self.now, ms, message);
+            }
+            Command::Every(ms, message) => {
+                self.timers.every(self.now, ms, message);
+            }
+            Command::Focus(index) => self.focus(index),
+            Command::FocusWidget(id) => {
+                if let Some(index) = self.find(id) {
+                    self.focus(index);
+                }
+            }
+            Command::FocusNext => self.focus_next(),
+            Command::FocusPrevious => self.focus_previous(),
+            Command::SetEnabled(id, enabled) => self.set_enabled(id, enabled),
+            Command::ScrollIntoView(id) => {
+                let bounds = self
+                    .app
+                    .view()
+                    .as_mut()
+                    .iter()
+                    .find(|widget| widget.id() == Some(id))
+                    .map(|widget| widget.bounding_box());
+                if let Some(bounds) = bounds {
+                    self.app.scroll_into_view(bounds);
+                    self.redraw = true;
+                }
+            }
+            Command::Navigate(screen) => {
+                self.app.navigate(screen);
+                self.focused = None;
+                self.router.reset();
+                self.redraw = true;
+            }
+            Command::Input(event) => self.input(event),
+        }
+    }
+
+    /// Hand `event` to the focused widget only, wherever the pointer is,
+    /// and queue its message. Does nothing without a focused widget.
+    pub fn input(&mut self, event: InputEvent) {
+        if matches!(event, InputEvent::KeyPress(_) | InputEvent::Key(_)) {
+            self.restart_blink();
+        }
+        self.drop_lost_focus();
+        let Some(index) = self.focused else {
+            return;
+        };
+        let mut view = self.app.view();
+        if let Some(widget) = view.as_mut().get_mut(index)
+            && let Some(message) = widget.handle_event(event).message()
+        {
+            let _ = self.queue.push_back((message, widget.id()));
+        }
+    }
+
+    fn restart_blink(&mut self) {
+        if let Some(blink) = &mut self.blink {
+            blink.restart(self.now);
+            self.set_cursor_visible(true);
+        }
+    }
+
+    fn set_cursor_visible(&mut self, visible: bool) {
+        let Some(index) = self.focused else {
+            return;
+        };
+        if let Some(widget) = self.app.view().as_mut().get_mut(index) {
+            widget.get_state_manager_mut().set_cursor_visible(visible);
+        }
+    }
+
+    /// Move the focus to the widget at `index` in the current view. Does
+    /// nothing if there is no such widget or it can't take the focus
+    /// because it is hidden, disabled or not interactive.
+    pub fn focus(&mut self, index: usize) {
+        let mut view = self.app.view();
+        let widgets = view.as_mut();
+        if !widgets
+            .get(index)
+            .is_some_and(|widget| can_focus(&**widget))
+        {
+            return;
+        }
+        for (i, widget) in widgets.iter_mut().enumerate() {
+            widget.get_state_manager_mut().set_focused(i == index);
+        }
+        drop(view);
+        self.focused = Some(index);
+        self.restart_blink();
+    }
+
+    /// Clear the focus once the focused widget was hidden or disabled, so
+    /// it doesn't keep taking input or drawing a focus ring
+    fn drop_lost_focus(&mut self) {
+        let Some(index) = self.focused else {
+            return;
+        };
+        let mut view = self.app.view();
+        let widgets = view.as_mut();
+        if widgets
+            .get(index)
+            .is_some_and(|widget| can_focus(&**widget))
+        {
+            return;
+        }
+        if let Some(widget) = widgets.get_mut(index) {
+            widget.get_state_manager_mut().set_focused(false);
+        }
+        drop(view);
+        self.focused = None;
+    }
+
+    /// Focus the next visible, enabled and interactive widget after the
+    /// focused one, wrapping around
+    pub fn focus_next(&mut self) {
+        if let Some(index) = self.focusable(false) {
+            self.focus(index);
+        }
+    }
+    /// Like [`Runtime::focus_next`], backwards
+    pub fn focus_previous(&mut self) {
+        if let Some(index) = self.focusable(true) {
+            self.focus(index);
+        }
+    }
+
+    fn focusable(&mut self, backwards: bool) -> Option<usize> {
+        let mut view = self.app.view();
+        let widgets = view.as_mut();
+        let len = widgets.len();
+        if len == 0 {
+            return None;
+        }
+        let start = match (self.focused, backwards) {
+            (Some(focused), false) => focused % len + 1,
+            (Some(focused), true) => focused % len + len - 1,
+            (None, false) => 0,
+            (None, true) => len.saturating_sub(1),
+        };
+        (0..len)
+            .map(|step| {
+                if backwards {
+                    (start + len - step) % len
+                } else {
+                    (start + step) % len
+                }
+            })
+            .find(|&index| can_focus(&*widgets[index]))
+    }
+
+    /// Index of the widget with id `id` in the current view
+    pub fn find(&mut self, id: WidgetId) -> Option<usize> {
+        self.app
+            .view()
+            .as_mut()
+            .iter()
+            .position(|widget| widget.id() == Some(id))
+    }
+
+    /// Enable or disable the widget with id `id`. Disabling the focused
+    /// widget clears the focus.
+    pub fn set_enabled(&mut self, id: WidgetId, enabled: bool) {
+        let mut view = self.app.view();
+        if let Some(widget) = view
+            .as_mut()
+            .iter_mut()
+            .find(|widget| widget.id() == Some(id))
+        {
+            widget.set_enabled(enabled);
+        }
+        drop(view);
+        self.drop_lost_focus();
+    }
+
+    /// Call after switching the locale of the theme: the application lays
+    /// out again and the next draw repaints the whole screen.
+    pub fn locale_changed(&mut self, theme: &T) {
+        self.app.relayout(theme.direction());
+        self.redraw = true;
+    }
+
+    /// Redraw the widgets that changed since the last draw. After
+    /// navigating or closing a popup, the screen is cleared and everything
+    /// is drawn.
+    pub fn draw(&mut self, target: &mut D, theme: &T) -> Result<(), D::Error> {
+        self.drop_lost_focus();
+        let overlays = self.open_overlays();
+        let overlays_changed = overlays != self.overlays;
+        if overlays_changed && !self.overlays.is_empty() {
+            // Whatever was under the old popup shows again
+            self.redraw = true;
+        }
+        if self.redraw {
+            target.clear(theme.background_color())?;
+            return self.draw_all(target, theme);
+        }
+        let mut drawn = false;
+        for widget in self.app.view().as_mut() {
+            if widget.is_dirty() {
+                if widget.is_visible() {
+                    widget.draw_with_theme(target, theme)?;
+                } else {
+                    // Just hidden, wipe it
+                    target.fill_solid(&widget.bounding_box(), theme.background_color())?;
+                }
+                widget.mark_clean();
+                drawn = true;
+            }
+        }
+        if drawn || overlays_changed {
+            self.draw_overlays(target, theme)?;
+        }
+        self.overlays = overlays;
+        Ok(())
+    }
+
+    /// Redraw every widget, e.g. after the screen was cleared
+    pub fn draw_all(&mut self, target: &mut D, theme: &T) -> Result<(), D::Error> {
+        self.drop_lost_focus();
+        let min = self.min_touch_target.filter(|_| cfg!(debug_assertions));
+        self.small_touch_targets.clear();
+        self.small_touch_target_count = 0;
+        for (index, widget) in self.app.view().as_mut().iter_mut().enumerate() {
+            if !widget.is_visible() {
+                widget.mark_clean();
+                continue;
+            }
+            widget.draw_with_theme(target, theme)?;
+            widget.mark_clean();
+            if let Some(min) = min {
+                let size = widget.bounding_box().size;
+                // A closed popup takes no space at all
+                if widget.get_state_manager().is_interactive()
+                    && !widget.bounding_box().is_zero_sized()
+                    && (size.width < min.width || size.height < min.height)
+                {
+                    // Only the first ones are listed, but all are counted
+                    let _ = self.small_touch_targets.push(index);
+                    self.small_touch_target_count += 1;
+                }
+            }
+        }
+        self.draw_overlays(target, theme)?;
+        self.overlays = self.open_overlays();
+        self.redraw = false;
+        Ok(())
+    }
+
+    fn draw_overlays(&mut self, target: &mut D, theme: &T) -> Result<(), D::Error> {
+        for widget in self.app.view().as_mut() {
+            if widget.is_visible() {
+                widget.draw_overlay(target, theme)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The popups of the view, up to 4
+    fn open_overlays(&mut self) -> Vec<Rectangle, 4> {
+        self.app
+            .view()
+            .as_mut()
+            .iter()
+            .filter(|widget| widget.is_visible())
+            .filter_map(|widget| widget.overlay())
+            .take(4)
+            .collect()
+    }
+}
+
+/// Whether `widget` can take the focus
+fn can_focus<M: Copy, W: Widget<M> + ?Sized>(widget: &W) -> bool {
+    widget.is_visible() && widget.is_enabled() && widget.get_state_manager().is_interactive()
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::mock_display::MockDisplay;
+
+    use super::*;
+    use core::convert::Infallible;
+
+    use crate::{
+        Response, StateManager, ThemedWidget, Widget, WidgetState,
+        themes::DefaultTheme,
+        widgets::{Button, Number, OnScreenKeyboard, PopupMenu, ProgressBar, Tabs},
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Message {
+        Increment,
+        Decrement,
+        /// Count up every 100 ms
+        Start,
+        /// Take one back after 50 ms
+        Undo,
+        Reset,
+        /// Disable the decrement button
+        Lock,
+    }
+
+    const INCREMENT: WidgetId = WidgetId(1);
+    const DECREMENT: WidgetId = WidgetId(2);
+
+    struct Counter<'a> {
+        count: Number<'a, Message>,
+        increment: Button<'a, Message, Rgb888>,
+        decrement: Button<'a, Message, Rgb888>,
+        last_sender: Option<WidgetId>,
+    }
+
+    type Display = MockDisplay<Rgb888>;
+
+    impl Application<Display, DefaultTheme<Rgb888>, Rgb888> for Counter<'_> {
+        type Message = Message;
+
+        fn update(&mut self, message: Message) -> Command<Message> {
+            match message {
+                Message::Increment => self.count.set(self.count.get() + 1),
+                Message::Decrement => self.count.set(self.count.get() - 1),
+                Message::Start => return Command::Every(100, Message::Increment),
+                Message::Undo => return Command::Delay(50, Message::Decrement),
+                Message::Reset => {
+                    self.count.set(0);
+                    return Command::Focus(2);
+                }
+                Message::Lock => return Command::SetEnabled(DECREMENT, false),
+            }
+            Command::None
+        }
+        fn update_from(&mut self, message: Message, sender: Option<WidgetId>) -> Command<Message> {
+            self.last_sender = sender;
+            self.update(message)
+        }
+        #[allow(refining_impl_trait)]
+        fn view(
+            &mut self,
+        ) -> [&mut dyn Element<Message, Display, DefaultTheme<Rgb888>, Rgb888>; 3] {
+            [&mut self.increment, &mut self.count, &mut self.decrement]
+        }
+    }
+
+    fn counter() -> Runtime<Counter<'static>, Display, DefaultTheme<Rgb888>, Rgb888> {
+        Runtime::new(Counter {
+            count: Number::new(Point::new(0, 20), Size::new(20, 20)),
+            increment: Button::new("+")
+                .on_press(Message::Increment)
+                .with_size(Size::new(10, 10))
+                .with_id(INCREMENT),
+            decrement: Button::new("-")
+                .on_press(Message::Decrement)
+                .with_position(Point::new(0, 50))
+                .with_size(Size::new(10, 10))
+                .with_id(DECREMENT),
+            last_sender: None,
+        })
+    }
+
+    #[test]
+    fn widget_messages_reach_update() {
+        let mut runtime = counter();
+
+        assert!(runtime.handle_event(InputEvent::MouseDown(Point::new(5, 5))));
+        assert!(!runtime.handle_event(InputEvent::MouseUp(Point::new(5, 5))));
+        assert!(runtime.handle_event(InputEvent::MouseDown(Point::new(5, 5))));
+        assert_eq!(runtime.app().count.get(), 2);
+
+        runtime.send(Message::Decrement).unwrap();
+        assert!(runtime.update());
+        assert_eq!(runtime.app().count.get(), 1);
+    }
+
+    #[test]
+    fn commands_run_on_the_fake_clock() {
+        let mut runtime = counter();
+        runtime.tick(1000);
+        runtime.send(Message::Start).unwrap();
+        runtime.send(Message::Undo).unwrap();
+        runtime.update();
+
+        assert!(!runtime.tick(1049));
+        assert!(runtime.tick(1050));
+        assert_eq!(runtime.app().count.get(), -1);
+        runtime.tick(1100);
+        assert_eq!(runtime.app().count.get(), 0);
+        // A late tick sends one message, not one per missed period
+        runtime.tick(1450);
+        assert_eq!(runtime.app().count.get(), 1);
+        runtime.tick(1500);
+        assert_eq!(runtime.app().count.get(), 2);
+
+        runtime.send(Message::Reset).unwrap();
+        runtime.update();
+        assert_eq!(runtime.focused(), Some(2));
+        assert_eq!(runtime.app().decrement.get_state(), WidgetState::Focused);
+
+        // Hovering the focused button and leaving again keeps the focus
+        runtime.handle_event(InputEvent::MouseMove(Point::new(5, 55)));
+        assert!(runtime.app().decrement.get_state_manager().is_hovered());
+        runtime.handle_event(InputEvent::MouseMove(Point::new(100, 100)));
+        assert_eq!(runtime.app().decrement.get_state(), WidgetState::Focused);
+    }
+
+    #[test]
+    fn widgets_are_found_by_id() {
+        let mut runtime = counter();
+        runtime.handle_event(InputEvent::MouseDown(Point::new(5, 5)));
+        assert_eq!(runtime.app().last_sender, Some(INCREMENT));
+        runtime.send(Message::Decrement).unwrap();
+        runtime.update();
+        assert_eq!(runtime.app().last_sender, None);
+
+        assert_eq!(runtime.find(DECREMENT), Some(2));
+        runtime.send(Message::Lock).unwrap();
+        runtime.update();
+        assert_eq!(runtime.app().decrement.get_state(), WidgetState::Disabled);
+        runtime.handle_event(InputEvent::MouseDown(Point::new(5, 55)));
+        assert_eq!(runtime.app().count.get(), 0);
+    }
+
+    #[test]
+    fn focus_skips_hidden_and_disabled_widgets() {
+        let mut runtime = counter();
+        runtime.focus_next();
+        assert_eq!(runtime.focused(), Some(0));
+        runtime.app_mut().count.set_visible(false);
+        runtime.focus_next();
+        assert_eq!(runtime.focused(), Some(2));
+
+        runtime.app_mut().count.set_visible(true);
+        runtime.app_mut().increment.set_enabled(false);
+        runtime.focus_next();
+        assert_eq!(runtime.focused(), Some(1));
+        runtime.focus_previous();
+        assert_eq!(runtime.focused(), Some(2));
+    }
+
+    #[test]
+    fn disabling_the_focused_widget_clears_the_focus() {
+        let mut runtime = counter();
+        runtime.focus(2);
+        runtime.run(Command::SetEnabled(DECREMENT, false));
+        assert_eq!(runtime.focused(), None);
+        assert!(!runtime.app().decrement.get_state_manager().is_focused());
+
+        // Hidden directly on the widget, it is noticed before the next event
+        runtime.focus(1);
+        runtime.app_mut().count.set_visible(false);
+        runtime.handle_event(InputEvent::KeyPress('x'));
+        assert_eq!(runtime.focused(), None);
+    }
+
+    #[test]
+    fn focus_ignores_missing_and_unfocusable_widgets() {
+        let mut runtime = counter();
+        runtime.run(Command::Focus(usize::MAX));
+        assert_eq!(runtime.focused(), None);
+        runtime.focus_previous();
+        assert_eq!(runtime.focused(), Some(2));
+
+        runtime.app_mut().count.set_visible(false);
+        runtime.focus(1);
+        assert_eq!(runtime.focused(), Some(2));
+        assert!(!runtime.app().count.get_state_manager().is_focused());
+    }
+
+    #[test]
+    fn small_touch_targets_are_reported() {
+        let mut runtime = counter().with_min_touch_target(Size::new(15, 15));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        display.set_allow_out_of_bounds_drawing(true);
+        runtime.draw(&mut display, &DefaultTheme::new()).unwrap();
+        // The buttons are 10x10, the 20x20 number is not interactive anyway
+        assert_eq!(runtime.small_touch_targets(), &[0, 2]);
+        assert_eq!(runtime.small_touch_target_count(), 2);
+    }
+
+    struct Menu<'a> {
+        button: Button<'a, Message, Rgb888>,
+        menu: PopupMenu<'a, Message, Rgb888, 2>,
+    }
+
+    impl Application<Display, DefaultTheme<Rgb888>, Rgb888> for Menu<'_> {
+        type Message = Message;
+
+        fn update(&mut self, _message: Message) -> Command<Message> {
+            Command::None
+        }
+        #[allow(refining_impl_trait)]
+        fn view(
+            &mut self,
+        ) -> [&mut dyn Element<Message, Display, DefaultTheme<Rgb888>, Rgb888>; 2] {
+            [&mut self.button, &mut self.menu]
+        }
+    }
+
+    #[test]
+    fn closing_a_popup_repaints_below() {
+        let theme = DefaultTheme::new();
+        let mut runtime: Runtime<_, Display, _, _> = Runtime::new(Menu {
+            button: Button::new("").with_size(Size::new(20, 20)),
+            menu: PopupMenu::new([("+", Message::Increment), ("-", Message::Decrement)])
+                .with_width(40)
+                .with_item_height(10),
+        })
+        .with_min_touch_target(Size::new(15, 15));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        display.set_allow_out_of_bounds_drawing(true);
+        runtime.draw(&mut display, &theme).unwrap();
+        // The closed menu has no size, but it isn't a small touch target
+        assert_eq!(runtime.small_touch_target_count(), 0);
+
+        let inside = Point::new(30, 45);
+        runtime.app_mut().menu.open_at(Point::new(10, 30));
+        runtime.draw(&mut display, &theme).unwrap();
+        assert_eq!(
+            display.get_pixel(inside),
+            Some(theme.panel_background_color())
+        );
+
+        // A tap outside closes the menu and clears it away
+        let outside = Point::new(60, 5);
+        runtime.handle_event(InputEvent::Touch(outside));
+        runtime.handle_event(InputEvent::TouchRelease(outside));
+        assert!(!runtime.app().menu.is_open());
+        runtime.draw(&mut display, &theme).unwrap();
+        assert_eq!(display.get_pixel(inside), Some(theme.background_color()));
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Paging {
+        Tab(usize),
+        Press,
+    }
+
+    struct Pages<'a> {
+        tabs: Tabs<'a, Paging, Rgb888, 2>,
+        level: ProgressBar<'a, Paging, Rgb888>,
+        button: Button<'a, Paging, Rgb888>,
+        tab: usize,
+        presses: u32,
+    }
+
+    impl Application<Display, DefaultTheme<Rgb888>, Rgb888> for Pages<'_> {
+        type Message = Paging;
+
+        fn update(&mut self, message: Paging) -> Command<Paging> {
+            match message {
+                Paging::Tab(index) => self.tab = index,
+                Paging::Press => self.presses += 1,
+            }
+            Command::None
+        }
+        #[allow(refining_impl_trait)]
+        fn view(&mut self) -> [&mut dyn Element<Paging, Display, DefaultTheme<Rgb888>, Rgb888>; 3] {
+            [&mut self.tabs, &mut self.level, &mut self.button]
+        }
+    }
+
+    #[test]
+    fn containers_get_swipes_nothing_inside_consumed() {
+        let tabs = Tabs::new(Rectangle::new(Point::zero(), Size::new(64, 64)), ["A", "B"])
+            .on_tab_changed(Paging::Tab);
+        let page = tabs.content().top_left;
+        let mut runtime: Runtime<_, Display, _, _> = Runtime::new(Pages {
+            tabs,
+            level: ProgressBar::new(page, Size::new(64, 10)),
+            button: Button::new("OK")
+                .on_press(Paging::Press)
+                .with_position(page + Point::new(0, 20))
+                .with_size(Size::new(64, 20)),
+            tab: 0,
+            presses: 0,
+        });
+        let swipe = |runtime: &mut Runtime<Pages, Display, _, _>, from: i32, to: i32, y: i32| {
+            runtime.handle_event(InputEvent::Touch(Point::new(from, y)));
+            runtime.handle_event(InputEvent::TouchMove(Point::new((from + to) / 2, y)));
+            runtime.handle_event(InputEvent::TouchRelease(Point::new(to, y)));
+        };
+
+        // The progress bar takes no press, so the swipe bubbles up to the tabs
+        swipe(&mut runtime, 60, 2, page.y + 5);
+        assert_eq!(runtime.app().tab, 1);
+
+        // The button consumes its press, the tabs don't see the swipe
+        swipe(&mut runtime, 2, 60, page.y + 30);
+        assert_eq!(runtime.app().tab, 1);
+        assert_eq!(runtime.app().presses, 1);
+
+        swipe(&mut runtime, 2, 60, page.y + 5);
+        assert_eq!(runtime.app().tab, 0);
+    }
+
+    /// Collects the characters typed into it
+    #[derive(Clone)]
+    struct Field {
+        text: heapless::String<8>,
+        bounds: Rectangle,
+        state_manager: StateManager,
+    }
+
+    impl Widget<Typed> for Field {
+        fn to_message(&self) -> Option<Typed> {
+            None
+        }
+        fn handle_event(&mut self, event: InputEvent) -> Response<Typed> {
+            match event {
+                InputEvent::KeyPress(c) => {
+                    let _ = self.text.push(c);
+                    Response::Consumed(None)
+                }
+                _ => Response::NotChanged,
+            }
+        }
+        fn get_state_manager(&self) -> &StateManager {
+            &self.state_manager
+        }
+        fn get_state_manager_mut(&mut self) -> &mut StateManager {
+            &mut self.state_manager
+        }
+    }
+    impl ThemedWidget<Display, DefaultTheme<Rgb888>, Rgb888> for Field {
+        fn draw_with_theme(
+            &self,
+            _: &mut Display,
+            _: &DefaultTheme<Rgb888>,
+        ) -> Result<(), Infallible> {
+            Ok(())
+        }
+    }
+    impl Element<Typed, Display, DefaultTheme<Rgb888>, Rgb888> for Field {}
+    impl Dimensions for Field {
+        fn bounding_box(&self) -> Rectangle {
+            self.bounds
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Typed(InputEvent);
+
+    struct Form<'a> {
+        field: Field,
+        keyboard: OnScreenKeyboard<'a, Typed, Rgb888>,
+    }
+
+    impl Application<Display, DefaultTheme<Rgb888>, Rgb888> for Form<'_> {
+        type Message = Typed;
+
+        fn update(&mut self, Typed(event): Typed) -> Command<Typed> {
+            Command::Input(event)
+        }
+        #[allow(refining_impl_trait)]
+        fn view(&mut self) -> [&mut dyn Element<Typed, Display, DefaultTheme<Rgb888>, Rgb888>; 2] {
+            [&mut self.field, &mut self.keyboard]
+        }
+    }
+
+    #[test]
+    fn keyboard_types_into_the_focused_widget() {
+        let mut runtime: Runtime<_, Display, _, _> = Runtime::new(Form {
+            field: Field {
+                text: heapless::String::new(),
+                bounds: Rectangle::new(Point::zero(), Size::new(64, 10)),
+                state_manager: StateManager::new(),
+            },
+            keyboard: OnScreenKeyboard::new(Size::new(64, 64)).on_key(Typed),
+        });
+        let tap = |runtime: &mut Runtime<Form, Display, _, _>, row, index| {
+            let point = runtime.app().keyboard.key_bounds(row, index).center();
+            runtime.handle_event(InputEvent::Touch(point));
+            runtime.handle_event(InputEvent::TouchRelease(point));
+        };
+
+        // Nothing is focused yet
+        tap(&mut runtime, 0, 0);
+        runtime.handle_event(InputEvent::KeyPress('a'));
+        assert_eq!(runtime.app().field.text, "");
+
+        runtime.focus(0);
+        tap(&mut runtime, 0, 0);
+        tap(&mut runtime, 0, 1);
+        // A hardware key goes the same way
+        runtime.handle_event(InputEvent::KeyPress('e'));
+        assert_eq!(runtime.app().field.text, "qwe");
+        // Tapping keys doesn't move the focus
+        assert_eq!(runtime.focused(), Some(0));
+
+        // Hidden, it loses the focus and gets nothing
+        runtime.app_mut().field.set_visible(false);
+        tap(&mut runtime, 0, 2);
+        assert_eq!(runtime.app().field.text, "qwe");
+        assert_eq!(runtime.focused(), None);
+    }
+
+    #[test]
+    fn idle_timeout_restarts_on_input() {
+        let mut runtime = counter().with_idle_timeout(1000, Message::Increment);
+        runtime.tick(900);
+        runtime.handle_event(InputEvent::MouseMove(Point::new(100, 100)));
+        runtime.tick(1800);
+        assert_eq!(runtime.app().count.get(), 0);
+        runtime.tick(1900);
+        runtime.tick(5000);
+        assert_eq!(runtime.app().count.get(), 1);
+    }
+}
@@ -0,0 +1,148 @@
+//! Binding widget values to model state.
+//!
+//! A model keeps its fields in [`Signal`]s, or exposes them through getter
+//! closures, and widgets hold a [`Binding`] to them instead of a copy of the
+//! value. The model stays the single source of truth: a bound widget reads
+//! the current value whenever it is drawn, and reports itself dirty as soon
+//! as the model changes.
+use core::cell::Cell;
+
+/// An observable value cell.
+///
+/// Signals use interior mutability, so a model can update them through a
+/// shared reference while widgets keep reading them.
+#[derive(Debug, Default)]
+pub struct Signal<T: Copy> {
+    value: Cell<T>,
+}
+
+impl<T: Copy> Signal<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            value: Cell::new(value),
+        }
+    }
+    pub fn get(&self) -> T {
+        self.value.get()
+    }
+    pub fn set(&self, value: T) {
+        self.value.set(value);
+    }
+    /// Replace the value with `f(value)`
+    pub fn update(&self, f: impl FnOnce(T) -> T) {
+        self.value.set(f(self.value.get()));
+    }
+}
+
+/// Where a widget gets its value from.
+#[derive(Clone, Copy)]
+pub enum Binding<'a, T: Copy> {
+    /// A value owned by the widget itself
+    Value(T),
+    /// A value owned by the model
+    Signal(&'a Signal<T>),
+    /// A value computed from the model on every read
+    Getter(&'a dyn Fn() -> T),
+}
+
+impl<T: Copy> Binding<'_, T> {
+    pub fn get(&self) -> T {
+        match self {
+            Binding::Value(value) => *value,
+            Binding::Signal(signal) => signal.get(),
+            Binding::Getter(getter) => getter(),
+        }
+    }
+
+    /// Write through to the bound value. A getter is read-only, so setting
+    /// a widget bound to one has no effect; change the model instead.
+    pub fn set(&mut self, value: T) {
+        match self {
+            Binding::Value(v) => *v = value,
+            Binding::Signal(signal) => signal.set(value),
+            Binding::Getter(_) => {}
+        }
+    }
+}
+
+impl<'a, T: Copy> From<&'a Signal<T>> for Binding<'a, T> {
+    fn from(signal: &'a Signal<T>) -> Self {
+        Binding::Signal(signal)
+    }
+}
+
+impl<'a, T: Copy> From<&'a dyn Fn() -> T> for Binding<'a, T> {
+    fn from(getter: &'a dyn Fn() -> T) -> Self {
+        Binding::Getter(getter)
+    }
+}
+
+/// A [`Binding`] that remembers the value it was last drawn with, so the
+/// widget holding it can tell when it needs a redraw.
+#[derive(Clone, Copy)]
+pub struct Bound<'a, T: Copy + PartialEq> {
+    binding: Binding<'a, T>,
+    drawn: Option<T>,
+}
+
+impl<'a, T: Copy + PartialEq> Bound<'a, T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            binding: Binding::Value(value),
+            drawn: None,
+        }
+    }
+    pub fn bind(&mut self, binding: Binding<'a, T>) {
+        self.binding = binding;
+        self.drawn = None;
+    }
+    pub fn get(&self) -> T {
+        self.binding.get()
+    }
+    pub fn set(&mut self, value: T) {
+        self.binding.set(value);
+    }
+    /// True if the value differs from the one last drawn
+    pub fn is_dirty(&self) -> bool {
+        self.drawn != Some(self.get())
+    }
+    pub fn mark_clean(&mut self) {
+        self.drawn = Some(self.get());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bound_value_tracks_signal() {
+        let signal = Signal::new(1);
+        let mut bound = Bound::new(0);
+        bound.bind(Binding::from(&signal));
+        assert!(bound.is_dirty());
+
+        bound.mark_clean();
+        assert!(!bound.is_dirty());
+
+        signal.update(|v| v + 1);
+        assert_eq!(bound.get(), 2);
+        assert!(bound.is_dirty());
+
+        // Writing through the binding changes the model
+        bound.set(5);
+        assert_eq!(signal.get(), 5);
+    }
+
+    #[test]
+    fn getter_is_read_only() {
+        let signal = Signal::new(3);
+        let double = || signal.get() * 2;
+        let mut bound = Bound::new(0);
+        bound.bind(Binding::Getter(&double));
+        assert_eq!(bound.get(), 6);
+
+        bound.set(1);
+        assert_eq!(bound.get(), 6);
+    }
+}
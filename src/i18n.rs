@@ -0,0 +1,199 @@
+//! Translated strings, fonts for other scripts and right-to-left layout.
+//!
+//! Widgets take a [`Text`], either a literal or a [`StringId`]. Ids are
+//! looked up in the [`Locale`] of the theme when drawing, so switching the
+//! locale of a [`Localized`](crate::themes::Localized) theme and redrawing
+//! is enough to translate the whole UI.
+//!
+//! ```
+//! use embui::i18n::{Direction, FontSet, Locale, StringId};
+//!
+//! const OK: StringId = StringId(0);
+//! const CANCEL: StringId = StringId(1);
+//!
+//! static EN: Locale = Locale::new("en", &["OK", "Cancel"]);
+//! static RU: Locale = Locale::new("ru", &["ОК", "Отмена"])
+//!     .with_fonts(&FontSet::CYRILLIC)
+//!     .with_fallback(&EN);
+//! static AR: Locale = Locale::new("ar", &["OK"])
+//!     .with_direction(Direction::Rtl)
+//!     .with_fallback(&EN);
+//!
+//! assert_eq!(RU.get(CANCEL), "Отмена");
+//! assert_eq!(AR.get(CANCEL), "Cancel");
+//! ```
+use embedded_graphics::{
+    mono_font::{MonoFont, iso_8859_1, iso_8859_5, jis_x0201},
+    prelude::*,
+    primitives::Rectangle,
+};
+use embedded_text::alignment::HorizontalAlignment;
+
+use crate::Theme;
+
+/// Index of a string in every [`Locale`] table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StringId(pub u16);
+
+/// Reading direction of a locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    #[default]
+    Ltr,
+    /// Right to left: layouts and text alignment are mirrored
+    Rtl,
+}
+
+impl Direction {
+    /// Swap left and right alignment for right-to-left text
+    pub fn align(self, alignment: HorizontalAlignment) -> HorizontalAlignment {
+        match (self, alignment) {
+            (Direction::Rtl, HorizontalAlignment::Left) => HorizontalAlignment::Right,
+            (Direction::Rtl, HorizontalAlignment::Right) => HorizontalAlignment::Left,
+            _ => alignment,
+        }
+    }
+
+    /// Mirror `bounds` inside `within` for right-to-left layouts
+    pub fn mirror(self, bounds: Rectangle, within: Rectangle) -> Rectangle {
+        match self {
+            Direction::Ltr => bounds,
+            Direction::Rtl => {
+                let left = bounds.top_left.x - within.top_left.x;
+                let x =
+                    within.top_left.x + within.size.width as i32 - left - bounds.size.width as i32;
+                Rectangle::new(Point::new(x, bounds.top_left.y), bounds.size)
+            }
+        }
+    }
+}
+
+/// The four font sizes of a [`Theme`], covering one character set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontSet {
+    pub small: &'static MonoFont<'static>,
+    pub normal: &'static MonoFont<'static>,
+    pub large: &'static MonoFont<'static>,
+    pub title: &'static MonoFont<'static>,
+}
+
+impl FontSet {
+    /// Western European languages
+    pub const LATIN_1: FontSet = FontSet {
+        small: &iso_8859_1::FONT_4X6,
+        normal: &iso_8859_1::FONT_6X10,
+        large: &iso_8859_1::FONT_8X13,
+        title: &iso_8859_1::FONT_10X20,
+    };
+    /// Russian, Ukrainian, Bulgarian and other Cyrillic scripts
+    pub const CYRILLIC: FontSet = FontSet {
+        small: &iso_8859_5::FONT_4X6,
+        normal: &iso_8859_5::FONT_6X10,
+        large: &iso_8859_5::FONT_8X13,
+        title: &iso_8859_5::FONT_10X20,
+    };
+    /// ASCII and half-width katakana, which only come in larger sizes.
+    /// Kanji need a font converted for the subset of characters the
+    /// application uses.
+    pub const KATAKANA: FontSet = FontSet {
+        small: &jis_x0201::FONT_6X13,
+        normal: &jis_x0201::FONT_7X14,
+        large: &jis_x0201::FONT_8X13,
+        title: &jis_x0201::FONT_10X20,
+    };
+}
+
+/// A string table for one language.
+#[derive(Debug, Clone, Copy)]
+pub struct Locale {
+    pub name: &'static str,
+    pub strings: &'static [&'static str],
+    pub direction: Direction,
+    /// Fonts covering the script, `None` keeps the theme's fonts
+    pub fonts: Option<&'static FontSet>,
+    /// Where to look up strings missing from this table
+    pub fallback: Option<&'static Locale>,
+}
+
+impl Locale {
+    pub const fn new(name: &'static str, strings: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            strings,
+            direction: Direction::Ltr,
+            fonts: None,
+            fallback: None,
+        }
+    }
+    pub const fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+    pub const fn with_fonts(mut self, fonts: &'static FontSet) -> Self {
+        self.fonts = Some(fonts);
+        self
+    }
+    pub const fn with_fallback(mut self, fallback: &'static Locale) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// The translation of `id`, from the fallbacks if this table lacks it,
+    /// or an empty string
+    pub fn get(&self, id: StringId) -> &'static str {
+        match self.strings.get(id.0 as usize) {
+            Some(string) => string,
+            None => self.fallback.map_or("", |fallback| fallback.get(id)),
+        }
+    }
+}
+
+/// Text shown by a widget.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Text<'a> {
+    /// Shown as is
+    Str(&'a str),
+    /// Looked up in the theme's locale
+    Id(StringId),
+}
+
+impl<'a> Text<'a> {
+    /// The string to draw with `theme`. Ids resolve to an empty string when
+    /// the theme has no locale.
+    pub fn resolve<C: PixelColor, T: Theme<C>>(&self, theme: &T) -> &'a str {
+        match self {
+            Text::Str(string) => string,
+            Text::Id(id) => theme.locale().map_or("", |locale| locale.get(*id)),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Text<'a> {
+    fn from(string: &'a str) -> Self {
+        Text::Str(string)
+    }
+}
+
+impl From<StringId> for Text<'_> {
+    fn from(id: StringId) -> Self {
+        Text::Id(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mirror_flips_within_container() {
+        let screen = Rectangle::new(Point::zero(), Size::new(100, 50));
+        let button = Rectangle::new(Point::new(10, 5), Size::new(30, 20));
+        let mirrored = Direction::Rtl.mirror(button, screen);
+        assert_eq!(
+            mirrored,
+            Rectangle::new(Point::new(60, 5), Size::new(30, 20))
+        );
+        assert_eq!(Direction::Rtl.mirror(mirrored, screen), button);
+        assert_eq!(Direction::Ltr.mirror(button, screen), button);
+    }
+}
@@ -0,0 +1,254 @@
+//! Rows, columns and the [`ui!`](crate::ui) macro.
+//!
+//! Layouts split a rectangle into equal cells along one axis and hand each
+//! cell to a widget through [`Place`]. The [`ui!`](crate::ui) macro builds a
+//! screen struct from a nested description of rows and columns, so widgets
+//! never need hand-computed positions.
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+use crate::widgets::Orientation;
+
+/// Widgets that can be moved and resized to fill a layout cell.
+pub trait Place {
+    fn place(&mut self, bounds: Rectangle);
+
+    /// Builder form of [`Place::place`]
+    fn placed(mut self, bounds: Rectangle) -> Self
+    where
+        Self: Sized,
+    {
+        self.place(bounds);
+        self
+    }
+}
+
+/// What a row or column does with the space of hidden widgets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Hidden {
+    /// Leave a gap, so nothing moves when a widget is shown again
+    #[default]
+    Keep,
+    /// Share the space among the visible widgets
+    Collapse,
+}
+
+/// Cell `index` of `count` equal cells along `orientation`, with `spacing`
+/// pixels between neighbours. Rounding leftovers are spread over the
+/// cells, so together they always fill `area` exactly.
+pub fn split(
+    area: Rectangle,
+    orientation: Orientation,
+    count: usize,
+    index: usize,
+    spacing: u32,
+) -> Rectangle {
+    let count = count.max(1) as u32;
+    let index = index as u32;
+    let length = match orientation {
+        Orientation::Horizontal => area.size.width,
+        Orientation::Vertical => area.size.height,
+    };
+    let free = length.saturating_sub(spacing * (count - 1));
+    let start = free * index / count + spacing * index;
+    let end = free * (index + 1) / count + spacing * index;
+    match orientation {
+        Orientation::Horizontal => Rectangle::new(
+            area.top_left + Point::new(start as i32, 0),
+            Size::new(end - start, area.size.height),
+        ),
+        Orientation::Vertical => Rectangle::new(
+            area.top_left + Point::new(0, start as i32),
+            Size::new(area.size.width, end - start),
+        ),
+    }
+}
+
+/// Declare a screen as nested rows and columns of widgets.
+///
+/// Expands to a struct with one field per widget, a `new` constructor that
+/// lays the widgets out inside the given bounds, and a `widgets` method
+/// returning them for [`Application::view`](crate::Application::view).
+/// The structure is fixed at compile time and the geometry is computed
+/// once, in `new`; there is no allocation and no runtime tree.
+///
+/// The body is a column. `row { .. }` and `column { .. }` nest, with an
+/// optional spacing in pixels: `row(4) { .. }`. Each widget is written as
+/// `name: Type = constructor`, where the constructor's own position and
+/// size are replaced by its cell. The parameters after the struct name are
+/// passed to `new` and can be used in the constructors, e.g. to bind to a
+/// model.
+///
+/// ```
+/// # use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+/// # use embui::{Signal, ui, widgets::{Button, Number}};
+/// #[derive(Clone, Copy)]
+/// enum Message {
+///     Increment,
+///     Decrement,
+/// }
+/// struct Model {
+///     count: Signal<i32>,
+/// }
+///
+/// ui! {
+///     pub struct Counter<'a>(model: &'a Model) for Message {
+///         count: Number<'a, Message> = Number::new(Point::zero(), Size::zero()).bind(&model.count),
+///         row(4) {
+///             decrement: Button<'a, Message, Rgb888> = Button::new("-").on_press(Message::Decrement),
+///             increment: Button<'a, Message, Rgb888> = Button::new("+").on_press(Message::Increment),
+///         }
+///     }
+/// }
+///
+/// let model = Model { count: Signal::new(0) };
+/// let counter = Counter::new(Rectangle::new(Point::zero(), Size::new(100, 60)), &model);
+/// assert_eq!(counter.increment.bounding_box().top_left, Point::new(52, 30));
+/// ```
+#[macro_export]
+macro_rules! ui {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident $(<$($lt:lifetime),+>)? ($($param:ident: $pty:ty),* $(,)?) for $msg:ty {
+            $($tree:tt)*
+        }
+    ) => {
+        $crate::ui!(@flatten
+            [$(#[$meta])* $vis struct $name $(<$($lt),+>)? ($($param: $pty),*) for $msg]
+            [$($tree)*]
+            []
+            ($($tree)*)
+        );
+    };
+
+    // Collect the widgets of the tree into a flat field list
+    (@flatten $header:tt $tree:tt [$($field:tt)*] ()) => {
+        $crate::ui!(@emit $header $tree [$($field)*]);
+    };
+    (@flatten $header:tt $tree:tt [$($field:tt)*] (, $($rest:tt)*)) => {
+        $crate::ui!(@flatten $header $tree [$($field)*] ($($rest)*));
+    };
+    (@flatten $header:tt $tree:tt [$($field:tt)*]
+        ($kind:ident $(($spacing:expr))? { $($inner:tt)* } $($rest:tt)*)
+    ) => {
+        $crate::ui!(@flatten $header $tree [$($field)*] ($($inner)* , $($rest)*));
+    };
+    (@flatten $header:tt $tree:tt [$($field:tt)*]
+        ($widget:ident : $ty:ty = $init:expr $(, $($rest:tt)*)?)
+    ) => {
+        $crate::ui!(@flatten $header $tree [$($field)* ($widget: $ty)] ($($($rest)*)?));
+    };
+
+    (@emit
+        [$(#[$meta:meta])* $vis:vis struct $name:ident $(<$($lt:lifetime),+>)? ($($param:ident: $pty:ty),*) for $msg:ty]
+        [$($tree:tt)*]
+        [$(($widget:ident: $ty:ty))*]
+    ) => {
+        $(#[$meta])*
+        $vis struct $name $(<$($lt),+>)? {
+            $($vis $widget: $ty,)*
+        }
+
+        impl $(<$($lt),+>)? $name $(<$($lt),+>)? {
+            /// Create every widget and lay them out inside `bounds`
+            #[allow(clippy::too_many_arguments)]
+            pub fn new(
+                bounds: $crate::__private::Rectangle,
+                $($param: $pty),*
+            ) -> Self {
+                $crate::ui!(@layout
+                    (bounds, $crate::widgets::Orientation::Vertical, 0)
+                    $crate::ui!(@count $($tree)*);
+                    0usize;
+                    $($tree)*
+                );
+                Self { $($widget),* }
+            }
+
+            /// All widgets, in drawing order
+            pub fn widgets<D, T, C>(
+                &mut self,
+            ) -> [&mut dyn $crate::screen::Element<$msg, D, T, C>; 0 $(+ $crate::ui!(@one $widget))*]
+            where
+                D: $crate::__private::DrawTarget<Color = C>,
+                T: $crate::Theme<C>,
+                C: $crate::__private::PixelColor + Default + From<$crate::__private::Rgb888>,
+                $($ty: $crate::screen::Element<$msg, D, T, C>,)*
+            {
+                [$(&mut self.$widget),*]
+            }
+        }
+    };
+
+    // Place each child of a container in its cell
+    (@layout $parent:tt $count:expr; $index:expr;) => {};
+    (@layout $parent:tt $count:expr; $index:expr; , $($rest:tt)*) => {
+        $crate::ui!(@layout $parent $count; $index; $($rest)*);
+    };
+    (@layout ($area:expr, $orientation:expr, $spacing:expr) $count:expr; $index:expr;
+        row $(($row_spacing:expr))? { $($inner:tt)* } $($rest:tt)*
+    ) => {
+        $crate::ui!(@layout
+            ($crate::layout::split($area, $orientation, $count, $index, $spacing),
+             $crate::widgets::Orientation::Horizontal,
+             $crate::ui!(@spacing $($row_spacing)?))
+            $crate::ui!(@count $($inner)*);
+            0usize;
+            $($inner)*
+        );
+        $crate::ui!(@layout ($area, $orientation, $spacing) $count; $index + 1; $($rest)*);
+    };
+    (@layout ($area:expr, $orientation:expr, $spacing:expr) $count:expr; $index:expr;
+        column $(($column_spacing:expr))? { $($inner:tt)* } $($rest:tt)*
+    ) => {
+        $crate::ui!(@layout
+            ($crate::layout::split($area, $orientation, $count, $index, $spacing),
+             $crate::widgets::Orientation::Vertical,
+             $crate::ui!(@spacing $($column_spacing)?))
+            $crate::ui!(@count $($inner)*);
+            0usize;
+            $($inner)*
+        );
+        $crate::ui!(@layout ($area, $orientation, $spacing) $count; $index + 1; $($rest)*);
+    };
+    (@layout ($area:expr, $orientation:expr, $spacing:expr) $count:expr; $index:expr;
+        $widget:ident : $ty:ty = $init:expr $(, $($rest:tt)*)?
+    ) => {
+        let $widget: $ty = $crate::layout::Place::placed(
+            $init,
+            $crate::layout::split($area, $orientation, $count, $index, $spacing),
+        );
+        $crate::ui!(@layout ($area, $orientation, $spacing) $count; $index + 1; $($($rest)*)?);
+    };
+
+    // Number of children of a container
+    (@count) => { 0usize };
+    (@count , $($rest:tt)*) => { $crate::ui!(@count $($rest)*) };
+    (@count $kind:ident $(($spacing:expr))? { $($inner:tt)* } $($rest:tt)*) => {
+        1 + $crate::ui!(@count $($rest)*)
+    };
+    (@count $widget:ident : $ty:ty = $init:expr $(, $($rest:tt)*)?) => {
+        1 + $crate::ui!(@count $($($rest)*)?)
+    };
+
+    (@spacing) => { 0 };
+    (@spacing $spacing:expr) => { $spacing };
+    (@one $widget:ident) => { 1 };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_fills_the_area() {
+        let area = Rectangle::new(Point::new(10, 0), Size::new(100, 20));
+        let cells: [Rectangle; 3] =
+            core::array::from_fn(|i| split(area, Orientation::Horizontal, 3, i, 5));
+        assert_eq!(
+            cells[0],
+            Rectangle::new(Point::new(10, 0), Size::new(30, 20))
+        );
+        assert_eq!(cells[1].top_left.x, 45);
+        assert_eq!(cells[2].top_left.x + cells[2].size.width as i32, 110);
+    }
+}
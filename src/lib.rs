@@ -1,53 +1,180 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
+// Lets `#[derive(Widget)]` refer to `::embui` inside this crate too
+extern crate self as embui;
 
-use embedded_graphics::{pixelcolor::Rgb888, prelude::*};
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
 
+pub mod app;
+pub mod binding;
+pub mod i18n;
+pub mod layout;
+pub mod router;
 pub mod screen;
+pub mod task;
 pub mod themes;
+pub mod timer;
+pub mod tree;
 pub mod widgets;
-use heapless::Vec;
+pub use app::{Application, Command, Runtime};
+pub use binding::{Binding, Signal};
+pub use embui_derive::Widget;
 pub use themes::Theme;
-pub use widgets::{StateManager, WidgetState};
+pub use widgets::{StateFlags, StateManager, WidgetId, WidgetState};
+
+/// Paths used by [`ui!`] and `#[derive(Widget)]`, not public API
+#[doc(hidden)]
+pub mod __private {
+    pub use embedded_graphics::{
+        draw_target::DrawTarget,
+        geometry::{Dimensions, Point},
+        pixelcolor::{PixelColor, Rgb888},
+        primitives::Rectangle,
+        transform::Transform,
+    };
+}
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InputEvent {
     Touch(Point),
+    /// A finger moved while down
+    TouchMove(Point),
     TouchRelease(Point),
     KeyPress(char),
+    Key(Key),
     MouseMove(Point),
+    /// The mouse moved onto the widget, sent by the [`router`]
+    MouseEnter(Point),
+    /// The mouse moved off the widget or onto one on top of it, sent by the
+    /// [`router`]
+    MouseLeave(Point),
     MouseDown(Point),
     MouseUp(Point),
 }
+/// Keys that don't type a character. Rotary encoders send `Up` and `Down`
+/// for each step and `Enter` when pushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Left,
+    Right,
+    Up,
+    Down,
+    Enter,
+    Escape,
+    Backspace,
+}
 #[derive(Debug, Clone, Copy)]
 pub enum Response<M: Clone + Copy> {
     Changed(Option<M>),
     NotChanged,
+    /// The widget handled the event, so the containers around it in a
+    /// [`tree`] don't see it
+    Consumed(Option<M>),
 }
+
+impl<M: Clone + Copy> Response<M> {
+    pub fn message(self) -> Option<M> {
+        match self {
+            Response::Changed(message) | Response::Consumed(message) => message,
+            Response::NotChanged => None,
+        }
+    }
+    pub fn is_consumed(&self) -> bool {
+        matches!(self, Response::Consumed(_))
+    }
+}
+
 /// A Widget handles events.
 pub trait Widget<M: Clone + Copy>: Dimensions {
     fn to_message(&self) -> Option<M>;
+    /// React to `event`. By default a widget consumes the events of a press
+    /// it holds: the press itself, the moves and the release, so a
+    /// container around a pressed button doesn't also act on them.
     fn handle_event(&mut self, event: InputEvent) -> Response<M> {
         let contains_point = match event {
             InputEvent::Touch(point)
+            | InputEvent::TouchMove(point)
             | InputEvent::TouchRelease(point)
             | InputEvent::MouseMove(point)
+            | InputEvent::MouseEnter(point)
+            | InputEvent::MouseLeave(point)
             | InputEvent::MouseDown(point)
             | InputEvent::MouseUp(point) => self.bounding_box().contains(point),
             _ => false,
         };
 
+        let was_pressed = self.get_state_manager().is_pressed();
         let state_changed = self
             .get_state_manager_mut()
             .handle_event(event, contains_point);
 
-        if state_changed {
-            Response::Changed(self.to_message())
+        let message = if state_changed {
+            self.to_message()
+        } else {
+            None
+        };
+        if was_pressed || self.get_state_manager().is_pressed() {
+            Response::Consumed(message)
+        } else if state_changed {
+            Response::Changed(message)
         } else {
             Response::NotChanged
         }
     }
+    /// The area of an open popup, like the list of a
+    /// [`Dropdown`](widgets::Dropdown), drawn above every other widget with
+    /// [`ThemedWidget::draw_overlay`]. While it is open the widget gets
+    /// every pointer event, so it can close when tapped outside.
+    fn overlay(&self) -> Option<Rectangle> {
+        None
+    }
+    /// Called on the containers of the widget an event is meant for before
+    /// it sees the event, outermost first, see
+    /// [`Arena::dispatch`](tree::Arena::dispatch). Return
+    /// [`Response::Consumed`] to keep the event from the widgets inside.
+    fn capture_event(&mut self, _event: InputEvent) -> Response<M> {
+        Response::NotChanged
+    }
+    /// For paged containers like [`Tabs`](widgets::Tabs), the index of the
+    /// only child a [`tree`] shows. The other children are hidden when they
+    /// are added, after the container handles an event and when it is
+    /// shown again.
+    fn active_child(&self) -> Option<usize> {
+        None
+    }
+
+    /// The id given with [`Widget::with_id`]
+    fn id(&self) -> Option<WidgetId> {
+        self.get_state_manager().id()
+    }
+    /// Give the widget an id, so the application can find it again and
+    /// tell which widget sent a message
+    fn with_id(mut self, id: WidgetId) -> Self
+    where
+        Self: Sized,
+    {
+        self.get_state_manager_mut().set_id(id);
+        self
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.get_state_manager().is_enabled()
+    }
+    /// Disabled widgets are drawn greyed out and ignore input. Returns true
+    /// if this changed anything.
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.get_state_manager_mut().set_enabled(enabled)
+    }
+    fn is_visible(&self) -> bool {
+        self.get_state_manager().is_visible()
+    }
+    /// Hidden widgets are not drawn, ignore input and are skipped when
+    /// moving the focus. Returns true if this changed anything.
+    fn set_visible(&mut self, visible: bool) -> bool {
+        self.get_state_manager_mut().set_visible(visible)
+    }
 
     fn get_state(&self) -> WidgetState {
         self.get_state_manager().current_state()
@@ -56,55 +183,35 @@ pub trait Widget<M: Clone + Copy>: Dimensions {
     fn set_state(&mut self, state: WidgetState) -> bool {
         self.get_state_manager_mut().set_state(state)
     }
+    /// Every flag that is set, for drawing combinations such as focused
+    /// and pressed
+    fn state_flags(&self) -> StateFlags {
+        self.get_state_manager().flags()
+    }
+
+    /// Whether the widget changed since it was last drawn
+    fn is_dirty(&self) -> bool {
+        self.get_state_manager().is_dirty()
+    }
+    /// Call after drawing the widget
+    fn mark_clean(&mut self) {
+        self.get_state_manager_mut().clear_dirty()
+    }
 
     fn get_state_manager(&self) -> &StateManager;
     fn get_state_manager_mut(&mut self) -> &mut StateManager;
 }
 pub trait ThemedWidget<D: DrawTarget, T: Theme<C>, C: PixelColor + Default + From<Rgb888>> {
     fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), D::Error>;
-}
-
-
-enum Parameter {
-
-}
-
-pub struct Element<'a, M, C> {
-    theme: &'a dyn Theme<C>,
-    widget: &'a mut dyn Widget<M>,
-    parent: Option<&'a Element<'a, M, C>>,
-    children: heapless::Vec<&'a Element<'a, M, C>, 8>,
-}
-
-impl<M, C> Drawable for Element<'_, M, C>
-where
-    C: PixelColor + Default,
-{
-    type Color = C;
-    type Output = ();
-
-    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
-    where
-        D: DrawTarget<Color = Self::Color>,
-    {
+    /// Draw the [`Widget::overlay`], after every widget has been drawn
+    fn draw_overlay(&self, _target: &mut D, _theme: &T) -> Result<(), D::Error> {
         Ok(())
     }
 }
 
-impl<'a, M, C> Element<'a, M, C> {
-    pub fn new(theme: &'a dyn Theme<C>, widget: &'a mut dyn Widget<M>) -> Self {
-        Self {
-            theme,
-            widget,
-            parent: None,
-            children: Vec::new(),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use crate::{Element, themes::DefaultTheme, widgets::Button};
+    use crate::{tree::Arena, widget_enum, widgets::Button};
     use embedded_graphics::pixelcolor::Rgb888;
 
     #[test]
@@ -113,8 +220,13 @@ mod tests {
         enum Message {
             Test,
         }
-        let theme = DefaultTheme::<Rgb888>::new();
-        let mut widget = Button::<Message, Rgb888>::new("test");
-        let n = Element::new(&theme, &mut widget);
+        widget_enum! {
+            enum Widgets<'a> for Message, Rgb888 {
+                Button(Button<'a, Message, Rgb888>),
+            }
+        }
+        let mut arena: Arena<Widgets, 1> = Arena::new();
+        let button = Button::new("test").on_press(Message::Test);
+        assert!(arena.insert(button).is_ok());
     }
 }
@@ -0,0 +1,425 @@
+//! Which widgets see an input event.
+//!
+//! Pointer events go to the topmost visible widget under the pointer, the
+//! last one in the view. A widget that takes a press captures the pointer:
+//! it gets every move and the release, wherever they happen, so a button
+//! that is pressed, dragged off and released always pops back up. Moving
+//! between widgets sends `MouseLeave` to the old one and `MouseEnter` to the
+//! new one. While a widget has an open [overlay](crate::Widget::overlay),
+//! such as a dropdown list, it gets every pointer event and every key
+//! press instead. Otherwise key presses are not routed here: the
+//! [`Runtime`](crate::Runtime) hands them to its focused widget.
+//!
+//! Presses, moves and releases also reach the containers of their widget,
+//! the earlier visible widgets in the view whose bounds contain it, as in
+//! [`Arena::dispatch`](crate::tree::Arena::dispatch): the containers see
+//! them in [`Widget::capture_event`] first, and in [`Widget::handle_event`]
+//! after the widget if it didn't consume them. So a [`Tabs`] listed before
+//! its pages gets swipes that start on a label, but not ones that start on a
+//! button. The innermost of them left pressed captures the pointer.
+//!
+//! [`Tabs`]: crate::widgets::Tabs
+//!
+//! Each finger on a multi-touch panel captures the widget it went down on.
+//! A widget stays pressed until the last finger on it is lifted.
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use heapless::Vec;
+
+use crate::{InputEvent, Widget, WidgetId};
+
+/// A finger on a touch panel, numbered by the panel's controller.
+///
+/// Single touch events ([`InputEvent::Touch`] and friends) are finger 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TouchId(pub u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchPhase {
+    Down,
+    Move,
+    Up,
+}
+
+/// Routes input to the widgets of a view, tracking hover, the captured
+/// mouse and up to `FINGERS` fingers.
+///
+/// Widgets are referred to by their index in the view, so call
+/// [`Router::reset`] when the view changes.
+#[derive(Debug, Clone)]
+pub struct Router<const FINGERS: usize = 2> {
+    hovered: Option<usize>,
+    mouse: Option<usize>,
+    fingers: Vec<(TouchId, usize), FINGERS>,
+}
+
+impl<const FINGERS: usize> Router<FINGERS> {
+    pub const fn new() -> Self {
+        Self {
+            hovered: None,
+            mouse: None,
+            fingers: Vec::new(),
+        }
+    }
+    /// Forget hover and every capture
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// The widget under the mouse
+    pub fn hovered(&self) -> Option<usize> {
+        self.hovered
+    }
+    /// The widget holding the mouse until the button goes up
+    pub fn captured(&self) -> Option<usize> {
+        self.mouse
+    }
+    /// The widget `finger` went down on
+    pub fn touched_by(&self, finger: TouchId) -> Option<usize> {
+        self.fingers
+            .iter()
+            .find(|&&(id, _)| id == finger)
+            .map(|&(_, index)| index)
+    }
+
+    /// Hand `event` to the widgets that should see it. `sent` is called
+    /// with every message produced and the id of the widget that sent it.
+    pub fn route<M, W>(
+        &mut self,
+        widgets: &mut [&mut W],
+        event: InputEvent,
+        mut sent: impl FnMut(M, Option<WidgetId>),
+    ) where
+        M: Copy,
+        W: Widget<M> + ?Sized,
+    {
+        match event {
+            InputEvent::MouseMove(point) | InputEvent::MouseEnter(point) => match self.mouse {
+                Some(index) => dispatch(widgets, index, event, &mut sent),
+                None => self.hover(widgets, point, &mut sent),
+            },
+            InputEvent::MouseLeave(_) => {
+                if let Some(index) = self.hovered.take() {
+                    deliver(widgets, index, event, &mut sent);
+                }
+            }
+            InputEvent::MouseDown(point) => {
+                if let Some(index) = topmost(widgets, point) {
+                    dispatch(widgets, index, event, &mut sent);
+                    self.mouse = pressed(widgets, index);
+                }
+            }
+            InputEvent::MouseUp(point) => {
+                if let Some(index) = self.mouse.take().or_else(|| topmost(widgets, point)) {
+                    dispatch(widgets, index, event, &mut sent);
+                }
+                // The pointer may have ended up over another widget
+                self.hover(widgets, point, &mut sent);
+            }
+            InputEvent::Touch(point) => {
+                self.touch(widgets, TouchId(0), TouchPhase::Down, point, sent)
+            }
+            InputEvent::TouchMove(point) => {
+                self.touch(widgets, TouchId(0), TouchPhase::Move, point, sent)
+            }
+            InputEvent::TouchRelease(point) => {
+                self.touch(widgets, TouchId(0), TouchPhase::Up, point, sent)
+            }
+            InputEvent::KeyPress(_) | InputEvent::Key(_) => {
+                if let Some(index) = popup(widgets) {
+                    deliver(widgets, index, event, &mut sent);
+                }
+            }
+        }
+    }
+
+    /// Hand a move of `finger` to the widget it captured. Fingers beyond
+    /// the first `FINGERS` are ignored.
+    pub fn touch<M, W>(
+        &mut self,
+        widgets: &mut [&mut W],
+        finger: TouchId,
+        phase: TouchPhase,
+        point: Point,
+        mut sent: impl FnMut(M, Option<WidgetId>),
+    ) where
+        M: Copy,
+        W: Widget<M> + ?Sized,
+    {
+        match phase {
+            TouchPhase::Down => {
+                if self.fingers.is_full() || self.touched_by(finger).is_some() {
+                    return;
+                }
+                if let Some(index) = topmost(widgets, point) {
+                    dispatch(widgets, index, InputEvent::Touch(point), &mut sent);
+                    if let Some(index) = pressed(widgets, index) {
+                        let _ = self.fingers.push((finger, index));
+                    }
+                }
+            }
+            TouchPhase::Move => {
+                if let Some(index) = self.touched_by(finger) {
+                    dispatch(widgets, index, InputEvent::TouchMove(point), &mut sent);
+                }
+            }
+            TouchPhase::Up => {
+                let Some(position) = self.fingers.iter().position(|&(id, _)| id == finger) else {
+                    return;
+                };
+                let (_, index) = self.fingers.swap_remove(position);
+                if !self.fingers.iter().any(|&(_, other)| other == index) {
+                    dispatch(widgets, index, InputEvent::TouchRelease(point), &mut sent);
+                }
+            }
+        }
+    }
+
+    fn hover<M, W>(
+        &mut self,
+        widgets: &mut [&mut W],
+        point: Point,
+        sent: &mut impl FnMut(M, Option<WidgetId>),
+    ) where
+        M: Copy,
+        W: Widget<M> + ?Sized,
+    {
+        let target = topmost(widgets, point);
+        if target == self.hovered {
+            if let Some(index) = target {
+                deliver(widgets, index, InputEvent::MouseMove(point), sent);
+            }
+            return;
+        }
+        if let Some(index) = self.hovered {
+            deliver(widgets, index, InputEvent::MouseLeave(point), sent);
+        }
+        if let Some(index) = target {
+            deliver(widgets, index, InputEvent::MouseEnter(point), sent);
+        }
+        self.hovered = target;
+    }
+}
+
+impl<const FINGERS: usize> Default for Router<FINGERS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The last visible widget containing `point`, which is drawn on top. An
+/// open popup takes every point, so a tap outside can close it.
+fn topmost<M, W>(widgets: &[&mut W], point: Point) -> Option<usize>
+where
+    M: Copy,
+    W: Widget<M> + ?Sized,
+{
+    popup(widgets).or_else(|| {
+        widgets
+            .iter()
+            .rposition(|widget| widget.is_visible() && widget.bounding_box().contains(point))
+    })
+}
+
+/// The last visible widget with an open overlay
+pub(crate) fn popup<M, W>(widgets: &[&mut W]) -> Option<usize>
+where
+    M: Copy,
+    W: Widget<M> + ?Sized,
+{
+    widgets
+        .iter()
+        .rposition(|widget| widget.is_visible() && widget.overlay().is_some())
+}
+
+/// How many containers around a widget see its events
+const DEPTH: usize = 8;
+
+/// `index` and its containers, innermost first. Containers come before
+/// their contents in a view, so they are the earlier visible widgets whose
+/// bounds contain the bounds of `index`.
+fn path<M, W>(widgets: &[&mut W], index: usize) -> Vec<usize, DEPTH>
+where
+    M: Copy,
+    W: Widget<M> + ?Sized,
+{
+    let mut path = Vec::new();
+    let Some(widget) = widgets.get(index) else {
+        return path;
+    };
+    let inner = widget.bounding_box();
+    let contains = |outer: Rectangle| {
+        outer.contains(inner.top_left)
+            && inner
+                .bottom_right()
+                .is_none_or(|corner| outer.contains(corner))
+    };
+    let _ = path.push(index);
+    for container in (0..index).rev() {
+        let widget = &widgets[container];
+        if widget.is_visible() && contains(widget.bounding_box()) && path.push(container).is_err() {
+            break;
+        }
+    }
+    path
+}
+
+/// The innermost widget on the path of `index` that holds a press
+fn pressed<M, W>(widgets: &[&mut W], index: usize) -> Option<usize>
+where
+    M: Copy,
+    W: Widget<M> + ?Sized,
+{
+    path(widgets, index)
+        .into_iter()
+        .find(|&index| widgets[index].get_state_manager().is_pressed())
+}
+
+/// Hand `event` to `index` and its containers until one consumes it, see
+/// the [module docs](self)
+fn dispatch<M, W>(
+    widgets: &mut [&mut W],
+    index: usize,
+    event: InputEvent,
+    sent: &mut impl FnMut(M, Option<WidgetId>),
+) where
+    M: Copy,
+    W: Widget<M> + ?Sized,
+{
+    let path = path(widgets, index);
+    let mut visit = |index: usize, capture: bool| {
+        let widget = &mut widgets[index];
+        if !widget.is_visible() {
+            return false;
+        }
+        let response = if capture {
+            widget.capture_event(event)
+        } else {
+            widget.handle_event(event)
+        };
+        if let Some(message) = response.message() {
+            sent(message, widget.id());
+        }
+        response.is_consumed()
+    };
+    let _ = path.iter().skip(1).rev().any(|&index| visit(index, true))
+        || path.iter().any(|&index| visit(index, false));
+}
+
+fn deliver<M, W>(
+    widgets: &mut [&mut W],
+    index: usize,
+    event: InputEvent,
+    sent: &mut impl FnMut(M, Option<WidgetId>),
+) where
+    M: Copy,
+    W: Widget<M> + ?Sized,
+{
+    let Some(widget) = widgets.get_mut(index) else {
+        return;
+    };
+    if !widget.is_visible() {
+        return;
+    }
+    if let Some(message) = widget.handle_event(event).message() {
+        sent(message, widget.id());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{pixelcolor::Rgb888, prelude::Size};
+
+    use super::*;
+    use crate::{WidgetState, widgets::Button};
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Message {
+        A,
+        B,
+    }
+
+    fn buttons() -> [Button<'static, Message, Rgb888>; 2] {
+        [
+            Button::new("A")
+                .on_press(Message::A)
+                .with_size(Size::new(20, 10)),
+            Button::new("B")
+                .on_press(Message::B)
+                .with_position(Point::new(0, 20))
+                .with_size(Size::new(20, 10)),
+        ]
+    }
+
+    #[test]
+    fn press_captures_the_pointer() {
+        let [mut a, mut b] = buttons();
+        let mut router: Router = Router::new();
+        let mut messages: heapless::Vec<Message, 4> = heapless::Vec::new();
+        let a_point = Point::new(5, 5);
+        let b_point = Point::new(5, 25);
+
+        for event in [
+            InputEvent::MouseMove(a_point),
+            InputEvent::MouseDown(a_point),
+            InputEvent::MouseMove(b_point),
+        ] {
+            router.route(&mut [&mut a, &mut b], event, |message, _| {
+                messages.push(message).unwrap()
+            });
+        }
+        assert_eq!(messages, [Message::A]);
+        assert_eq!(router.captured(), Some(0));
+        assert_eq!(a.get_state(), WidgetState::Pressed);
+        assert_eq!(b.get_state(), WidgetState::Normal);
+
+        // Released over B: A pops up and B is hovered
+        router.route(
+            &mut [&mut a, &mut b],
+            InputEvent::MouseUp(b_point),
+            |message, _| messages.push(message).unwrap(),
+        );
+        assert_eq!(messages, [Message::A]);
+        assert_eq!(a.get_state(), WidgetState::Normal);
+        assert_eq!(b.get_state(), WidgetState::Hovered);
+        assert_eq!(router.hovered(), Some(1));
+    }
+
+    fn touch(
+        router: &mut Router,
+        widgets: &mut [Button<'static, Message, Rgb888>; 2],
+        finger: u8,
+        phase: TouchPhase,
+        y: i32,
+    ) -> [bool; 2] {
+        let [a, b] = widgets;
+        router.touch(
+            &mut [a, b],
+            TouchId(finger),
+            phase,
+            Point::new(5, y),
+            |_, _| {},
+        );
+        widgets
+            .each_ref()
+            .map(|widget| widget.get_state_manager().is_pressed())
+    }
+
+    #[test]
+    fn fingers_are_tracked_apart() {
+        let mut widgets = buttons();
+        let mut router: Router = Router::new();
+
+        touch(&mut router, &mut widgets, 0, TouchPhase::Down, 5);
+        let pressed = touch(&mut router, &mut widgets, 1, TouchPhase::Down, 25);
+        assert_eq!(pressed, [true, true]);
+        let pressed = touch(&mut router, &mut widgets, 1, TouchPhase::Up, 25);
+        assert_eq!(pressed, [true, false]);
+
+        // A second finger on A keeps it down when the first one lifts
+        touch(&mut router, &mut widgets, 1, TouchPhase::Down, 5);
+        let pressed = touch(&mut router, &mut widgets, 0, TouchPhase::Up, 25);
+        assert_eq!(pressed, [true, false]);
+        let pressed = touch(&mut router, &mut widgets, 1, TouchPhase::Up, 5);
+        assert_eq!(pressed, [false, false]);
+    }
+}
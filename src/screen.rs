@@ -33,6 +33,9 @@ where
         for e in self.as_slice() {
             e.draw_with_theme(target, theme)?
         }
+        for e in self.as_slice() {
+            e.draw_overlay(target, theme)?
+        }
         Ok(())
     }
 }
@@ -0,0 +1,242 @@
+//! An async UI loop.
+//!
+//! [`run`] only uses `core::future`, so it works with any executor: spawn it
+//! as an embassy task on the device, or drive it with a simple `block_on`
+//! on the host. Input and the clock come in through the [`Inbox`] and
+//! [`Clock`] traits, which are thin wrappers around whatever channel and
+//! timer the executor provides. With embassy:
+//!
+//! ```ignore
+//! struct UiInbox(Receiver<'static, CriticalSectionRawMutex, Input<Message>, 8>);
+//!
+//! impl Inbox<Message> for UiInbox {
+//!     async fn receive(&mut self) -> Option<Input<Message>> {
+//!         Some(self.0.receive().await)
+//!     }
+//! }
+//!
+//! struct EmbassyClock;
+//!
+//! impl Clock for EmbassyClock {
+//!     fn now(&self) -> u64 {
+//!         Instant::now().as_millis()
+//!     }
+//!     async fn sleep_until(&mut self, deadline: u64) {
+//!         Timer::at(Instant::from_millis(deadline)).await
+//!     }
+//! }
+//! ```
+use core::{
+    future::{Future, poll_fn},
+    pin::pin,
+    task::Poll,
+};
+
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*};
+
+use crate::{
+    InputEvent, Theme,
+    app::{Application, Runtime},
+};
+
+/// Something for the UI task to handle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Input<M> {
+    /// Touch, mouse or key input
+    Event(InputEvent),
+    /// A message from another task, e.g. a new sensor reading
+    Message(M),
+}
+
+/// Where the UI task receives input from, usually a channel shared with
+/// the input driver and other tasks.
+pub trait Inbox<M> {
+    /// Wait for the next input. `None` means the channel closed and the UI
+    /// task should stop.
+    ///
+    /// The future is dropped when a timer fires first, so it must not lose
+    /// input when cancelled. Receiving from an embassy `Channel` is fine.
+    fn receive(&mut self) -> impl Future<Output = Option<Input<M>>>;
+}
+
+/// A monotonic millisecond clock.
+pub trait Clock {
+    fn now(&self) -> u64;
+    /// Wait until `now()` reaches `deadline`
+    fn sleep_until(&mut self, deadline: u64) -> impl Future<Output = ()>;
+}
+
+enum Wake<M> {
+    Input(Option<Input<M>>),
+    Timer,
+}
+
+/// Run `runtime` until `inbox` closes.
+///
+/// Each turn redraws the widgets that are dirty, then sleeps until input
+/// arrives or the next timer is due. Nothing is drawn while nothing
+/// changes.
+pub async fn run<A, D, T, C, const Q: usize>(
+    runtime: &mut Runtime<A, D, T, C, Q>,
+    display: &mut D,
+    theme: &T,
+    inbox: &mut impl Inbox<A::Message>,
+    clock: &mut impl Clock,
+) -> Result<(), D::Error>
+where
+    A: Application<D, T, C>,
+    D: DrawTarget<Color = C> + 'static,
+    T: Theme<C> + 'static,
+    C: PixelColor + Default + From<Rgb888> + 'static,
+{
+    loop {
+        runtime.tick(clock.now());
+        runtime.draw(display, theme)?;
+
+        let deadline = runtime.next_deadline();
+        let wake = {
+            let mut input = pin!(inbox.receive());
+            let mut sleep = pin!(deadline.map(|deadline| clock.sleep_until(deadline)));
+            poll_fn(|cx| {
+                if let Poll::Ready(input) = input.as_mut().poll(cx) {
+                    return Poll::Ready(Wake::Input(input));
+                }
+                match sleep.as_mut().as_pin_mut().map(|sleep| sleep.poll(cx)) {
+                    Some(Poll::Ready(())) => Poll::Ready(Wake::Timer),
+                    _ => Poll::Pending,
+                }
+            })
+            .await
+        };
+
+        match wake {
+            // The next tick handles whatever was due
+            Wake::Timer => {}
+            Wake::Input(None) => return Ok(()),
+            Wake::Input(Some(input)) => {
+                runtime.tick(clock.now());
+                match input {
+                    Input::Event(event) => {
+                        runtime.handle_event(event);
+                    }
+                    Input::Message(message) => {
+                        let _ = runtime.send(message);
+                        runtime.update();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{cell::Cell, pin::pin, task::Context};
+
+    use embedded_graphics::mock_display::MockDisplay;
+    use heapless::Deque;
+
+    use super::*;
+    use crate::{Command, Widget, screen::Element, themes::DefaultTheme, widgets::Number};
+
+    /// Poll `future` to completion, for futures that never actually wait
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(core::task::Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Message {
+        Start,
+        Tick,
+    }
+
+    struct Ticker {
+        ticks: Number<'static, Message>,
+    }
+
+    type Display = MockDisplay<Rgb888>;
+
+    impl Application<Display, DefaultTheme<Rgb888>, Rgb888> for Ticker {
+        type Message = Message;
+
+        fn update(&mut self, message: Message) -> Command<Message> {
+            match message {
+                Message::Start => Command::Every(100, Message::Tick),
+                Message::Tick => {
+                    self.ticks.set(self.ticks.get() + 1);
+                    Command::None
+                }
+            }
+        }
+        #[allow(refining_impl_trait)]
+        fn view(
+            &mut self,
+        ) -> [&mut dyn Element<Message, Display, DefaultTheme<Rgb888>, Rgb888>; 1] {
+            [&mut self.ticks]
+        }
+    }
+
+    /// Sleeping jumps straight to the deadline
+    struct FakeClock<'a>(&'a Cell<u64>);
+
+    impl Clock for FakeClock<'_> {
+        fn now(&self) -> u64 {
+            self.0.get()
+        }
+        async fn sleep_until(&mut self, deadline: u64) {
+            self.0.set(deadline);
+        }
+    }
+
+    /// Hands out its inputs, then waits until `close_at` and closes
+    struct Script<'a> {
+        clock: &'a Cell<u64>,
+        inputs: Deque<Input<Message>, 4>,
+        close_at: u64,
+    }
+
+    impl Inbox<Message> for Script<'_> {
+        fn receive(&mut self) -> impl Future<Output = Option<Input<Message>>> {
+            poll_fn(|_| match self.inputs.pop_front() {
+                Some(input) => Poll::Ready(Some(input)),
+                None if self.clock.get() >= self.close_at => Poll::Ready(None),
+                None => Poll::Pending,
+            })
+        }
+    }
+
+    #[test]
+    fn timers_wake_the_loop() {
+        let now = Cell::new(0);
+        let mut inputs = Deque::new();
+        inputs.push_back(Input::Message(Message::Start)).unwrap();
+        let mut inbox = Script {
+            clock: &now,
+            inputs,
+            close_at: 350,
+        };
+        let mut runtime: Runtime<_, _, _, _> = Runtime::new(Ticker {
+            ticks: Number::new(Point::new(8, 8), Size::new(20, 10)),
+        });
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+
+        block_on(run(
+            &mut runtime,
+            &mut display,
+            &DefaultTheme::new(),
+            &mut inbox,
+            &mut FakeClock(&now),
+        ))
+        .unwrap();
+        // Woken at 100, 200, 300 and 400, then the inbox closed
+        assert_eq!(runtime.app().ticks.get(), 4);
+        assert!(!runtime.app().ticks.is_dirty());
+    }
+}
@@ -0,0 +1,321 @@
+use embedded_graphics::{
+    mono_font::MonoFont,
+    mono_font::ascii::{FONT_4X6, FONT_6X10, FONT_8X13, FONT_10X20},
+    pixelcolor::Rgb888,
+    prelude::*,
+};
+
+use crate::{
+    Theme, WidgetState,
+    i18n::{Direction, Locale},
+};
+
+/// White on black with yellow accents, for the high-contrast mode of
+/// [`Accessible`]. It can also be used on its own.
+#[derive(Debug, Clone, Copy)]
+pub struct HighContrastTheme<C: PixelColor> {
+    _phantom: core::marker::PhantomData<C>,
+}
+
+impl<C: PixelColor> HighContrastTheme<C> {
+    pub const fn new() -> Self {
+        Self {
+            _phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<C: PixelColor> Default for HighContrastTheme<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Theme<C> for HighContrastTheme<C>
+where
+    C: PixelColor + From<Rgb888>,
+{
+    fn primary_color(&self) -> C {
+        C::from(Rgb888::CSS_YELLOW)
+    }
+    fn primary_dark(&self) -> C {
+        C::from(Rgb888::CSS_YELLOW)
+    }
+    fn primary_light(&self) -> C {
+        C::from(Rgb888::CSS_NAVY)
+    }
+    fn secondary_color(&self) -> C {
+        C::from(Rgb888::CSS_CYAN)
+    }
+    fn background_color(&self) -> C {
+        C::from(Rgb888::BLACK)
+    }
+    fn surface_color(&self) -> C {
+        C::from(Rgb888::BLACK)
+    }
+    fn text_primary(&self) -> C {
+        C::from(Rgb888::WHITE)
+    }
+    fn text_secondary(&self) -> C {
+        C::from(Rgb888::WHITE)
+    }
+    fn text_disabled(&self) -> C {
+        C::from(Rgb888::CSS_GRAY)
+    }
+    fn border_color(&self) -> C {
+        C::from(Rgb888::WHITE)
+    }
+    fn error_color(&self) -> C {
+        C::from(Rgb888::CSS_RED)
+    }
+    fn success_color(&self) -> C {
+        C::from(Rgb888::CSS_LIME)
+    }
+    fn warning_color(&self) -> C {
+        C::from(Rgb888::CSS_ORANGE)
+    }
+
+    fn small_font(&self) -> &'static MonoFont<'static> {
+        &FONT_4X6
+    }
+    fn normal_font(&self) -> &'static MonoFont<'static> {
+        &FONT_6X10
+    }
+    fn large_font(&self) -> &'static MonoFont<'static> {
+        &FONT_8X13
+    }
+    fn title_font(&self) -> &'static MonoFont<'static> {
+        &FONT_10X20
+    }
+
+    fn button_border_width(&self) -> u32 {
+        2
+    }
+    fn focus_ring_width(&self) -> u32 {
+        2
+    }
+}
+
+/// The smallest comfortable touch target, `mm` millimetres square, on a
+/// display with `dpi` pixels per inch. Rounds up.
+pub const fn touch_target_size(mm: u32, dpi: u32) -> Size {
+    let px = (mm * dpi * 10).div_ceil(254);
+    Size::new(px, px)
+}
+
+/// Accessibility settings on top of any theme.
+///
+/// The text scale moves every font that many sizes up the theme's own
+/// small, normal, large, title ladder. High contrast swaps all colours for
+/// [`HighContrastTheme`] and thickens borders and focus rings, while
+/// spacing and shapes keep following the wrapped theme. Both can be changed
+/// at runtime; redraw everything afterwards.
+#[derive(Debug, Clone, Copy)]
+pub struct Accessible<T, C: PixelColor> {
+    theme: T,
+    high_contrast_theme: HighContrastTheme<C>,
+    text_scale: u8,
+    high_contrast: bool,
+}
+
+impl<T, C> Accessible<T, C>
+where
+    T: Theme<C>,
+    C: PixelColor + From<Rgb888>,
+{
+    pub const fn new(theme: T) -> Self {
+        Self {
+            theme,
+            high_contrast_theme: HighContrastTheme::new(),
+            text_scale: 0,
+            high_contrast: false,
+        }
+    }
+    pub fn with_text_scale(mut self, steps: u8) -> Self {
+        self.text_scale = steps;
+        self
+    }
+    pub fn with_high_contrast(mut self, high_contrast: bool) -> Self {
+        self.high_contrast = high_contrast;
+        self
+    }
+    pub fn set_text_scale(&mut self, steps: u8) {
+        self.text_scale = steps;
+    }
+    pub fn set_high_contrast(&mut self, high_contrast: bool) {
+        self.high_contrast = high_contrast;
+    }
+    pub fn text_scale(&self) -> u8 {
+        self.text_scale
+    }
+    pub fn is_high_contrast(&self) -> bool {
+        self.high_contrast
+    }
+    pub fn inner(&self) -> &T {
+        &self.theme
+    }
+
+    fn colors(&self) -> &dyn Theme<C> {
+        if self.high_contrast {
+            &self.high_contrast_theme
+        } else {
+            &self.theme
+        }
+    }
+    fn layout(&self) -> &T {
+        &self.theme
+    }
+    /// The font `size` steps up the ladder, plus the text scale
+    fn font(&self, size: usize) -> &'static MonoFont<'static> {
+        let fonts = [
+            self.theme.small_font(),
+            self.theme.normal_font(),
+            self.theme.large_font(),
+            self.theme.title_font(),
+        ];
+        fonts[(size + self.text_scale as usize).min(fonts.len() - 1)]
+    }
+    /// Thicker lines in high contrast
+    fn width(&self, width: u32) -> u32 {
+        if self.high_contrast {
+            width.max(2)
+        } else {
+            width
+        }
+    }
+}
+
+impl<T, C> Theme<C> for Accessible<T, C>
+where
+    T: Theme<C>,
+    C: PixelColor + From<Rgb888>,
+{
+    forward! { colors =>
+        primary_color() -> C;
+        primary_dark() -> C;
+        primary_light() -> C;
+        secondary_color() -> C;
+        background_color() -> C;
+        surface_color() -> C;
+        text_primary() -> C;
+        text_secondary() -> C;
+        text_disabled() -> C;
+        border_color() -> C;
+        error_color() -> C;
+        success_color() -> C;
+        warning_color() -> C;
+        shadow_color() -> C;
+        bevel_light_color() -> C;
+        bevel_dark_color() -> C;
+        focus_ring_color() -> C;
+        button_normal_bg() -> C;
+        button_normal_text() -> C;
+        button_normal_border() -> C;
+        button_hovered_bg() -> C;
+        button_hovered_text() -> C;
+        button_hovered_border() -> C;
+        button_pressed_bg() -> C;
+        button_pressed_text() -> C;
+        button_pressed_border() -> C;
+        button_disabled_bg() -> C;
+        button_disabled_text() -> C;
+        button_disabled_border() -> C;
+        button_shadow_offset() -> Option<Point>;
+        button_bevel() -> bool;
+        button_gradient(state: WidgetState) -> Option<(Rgb888, Rgb888)>;
+        label_text_color() -> C;
+        label_disabled_text_color() -> C;
+        label_background_color() -> Option<C>;
+        panel_background_color() -> C;
+        panel_border_color() -> C;
+        textbox_background_color() -> C;
+        textbox_text_color() -> C;
+        textbox_border_color() -> C;
+        textbox_focused_border_color() -> C;
+        textbox_cursor_color() -> C;
+        textbox_selection_color() -> C;
+        chart_background_color() -> C;
+        chart_grid_color() -> C;
+        chart_axis_text_color() -> C;
+        chart_series_color(index: usize) -> C;
+        meter_track_color() -> C;
+        meter_fill_color() -> C;
+        meter_border_color() -> C;
+        meter_needle_color() -> C;
+        meter_peak_color() -> C;
+    }
+
+    forward! { layout =>
+        spacing_xs() -> u32;
+        spacing_sm() -> u32;
+        spacing_md() -> u32;
+        spacing_lg() -> u32;
+        spacing_xl() -> u32;
+        spacing_xxl() -> u32;
+        button_pressed_offset() -> Point;
+        button_corner_radius() -> u32;
+        panel_corner_radius() -> u32;
+        panel_padding() -> u32;
+        textbox_padding() -> u32;
+        locale() -> Option<&'static Locale>;
+        direction() -> Direction;
+    }
+
+    fn small_font(&self) -> &'static MonoFont<'static> {
+        self.font(0)
+    }
+    fn normal_font(&self) -> &'static MonoFont<'static> {
+        self.font(1)
+    }
+    fn large_font(&self) -> &'static MonoFont<'static> {
+        self.font(2)
+    }
+    fn title_font(&self) -> &'static MonoFont<'static> {
+        self.font(3)
+    }
+
+    fn focus_ring_width(&self) -> u32 {
+        self.width(self.theme.focus_ring_width())
+    }
+    fn button_border_width(&self) -> u32 {
+        self.width(self.theme.button_border_width())
+    }
+    fn panel_border_width(&self) -> u32 {
+        self.width(self.theme.panel_border_width())
+    }
+    fn textbox_border_width(&self) -> u32 {
+        self.width(self.theme.textbox_border_width())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::themes::DefaultTheme;
+
+    #[test]
+    fn touch_target_in_pixels() {
+        assert_eq!(touch_target_size(9, 160), Size::new(57, 57));
+        assert_eq!(touch_target_size(254, 100), Size::new(1000, 1000));
+    }
+
+    #[test]
+    fn text_scale_moves_fonts_up() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let large = Accessible::new(DefaultTheme::<Rgb888>::new()).with_text_scale(1);
+        assert_eq!(large.small_font(), theme.normal_font());
+        assert_eq!(large.normal_font(), theme.large_font());
+        assert_eq!(large.title_font(), theme.title_font());
+    }
+
+    #[test]
+    fn high_contrast_swaps_colors_only() {
+        let mut theme = Accessible::new(DefaultTheme::<Rgb888>::new());
+        assert_eq!(theme.background_color(), Rgb888::CSS_WHITE);
+        theme.set_high_contrast(true);
+        assert_eq!(theme.background_color(), Rgb888::BLACK);
+        assert_eq!(theme.button_normal_text(), Rgb888::WHITE);
+        assert_eq!(theme.button_border_width(), 2);
+        assert_eq!(theme.spacing_md(), theme.inner().spacing_md());
+    }
+}
@@ -0,0 +1,162 @@
+use embedded_graphics::{mono_font::MonoFont, pixelcolor::Rgb888, prelude::*};
+
+use crate::{
+    Theme, WidgetState,
+    i18n::{Direction, Locale},
+};
+
+/// Any theme with a [`Locale`] attached.
+///
+/// Fonts come from the locale's [`FontSet`](crate::i18n::FontSet) when it
+/// has one, everything else from the wrapped theme. After
+/// [`Localized::set_locale`], call
+/// [`Runtime::locale_changed`](crate::Runtime::locale_changed) so the
+/// application can lay out again and every widget is redrawn.
+#[derive(Debug, Clone, Copy)]
+pub struct Localized<T> {
+    theme: T,
+    locale: &'static Locale,
+}
+
+impl<T> Localized<T> {
+    pub const fn new(theme: T, locale: &'static Locale) -> Self {
+        Self { theme, locale }
+    }
+    pub fn set_locale(&mut self, locale: &'static Locale) {
+        self.locale = locale;
+    }
+    pub fn inner(&self) -> &T {
+        &self.theme
+    }
+    fn theme(&self) -> &T {
+        &self.theme
+    }
+}
+
+impl<T, C> Theme<C> for Localized<T>
+where
+    T: Theme<C>,
+    C: PixelColor + From<Rgb888>,
+{
+    fn locale(&self) -> Option<&'static Locale> {
+        Some(self.locale)
+    }
+    fn direction(&self) -> Direction {
+        self.locale.direction
+    }
+
+    fn small_font(&self) -> &'static MonoFont<'static> {
+        self.locale
+            .fonts
+            .map_or_else(|| self.theme.small_font(), |fonts| fonts.small)
+    }
+    fn normal_font(&self) -> &'static MonoFont<'static> {
+        self.locale
+            .fonts
+            .map_or_else(|| self.theme.normal_font(), |fonts| fonts.normal)
+    }
+    fn large_font(&self) -> &'static MonoFont<'static> {
+        self.locale
+            .fonts
+            .map_or_else(|| self.theme.large_font(), |fonts| fonts.large)
+    }
+    fn title_font(&self) -> &'static MonoFont<'static> {
+        self.locale
+            .fonts
+            .map_or_else(|| self.theme.title_font(), |fonts| fonts.title)
+    }
+
+    forward! { theme =>
+        primary_color() -> C;
+        primary_dark() -> C;
+        primary_light() -> C;
+        secondary_color() -> C;
+        background_color() -> C;
+        surface_color() -> C;
+        text_primary() -> C;
+        text_secondary() -> C;
+        text_disabled() -> C;
+        border_color() -> C;
+        error_color() -> C;
+        success_color() -> C;
+        warning_color() -> C;
+        spacing_xs() -> u32;
+        spacing_sm() -> u32;
+        spacing_md() -> u32;
+        spacing_lg() -> u32;
+        spacing_xl() -> u32;
+        spacing_xxl() -> u32;
+        shadow_color() -> C;
+        bevel_light_color() -> C;
+        bevel_dark_color() -> C;
+        focus_ring_width() -> u32;
+        focus_ring_color() -> C;
+        button_normal_bg() -> C;
+        button_normal_text() -> C;
+        button_normal_border() -> C;
+        button_hovered_bg() -> C;
+        button_hovered_text() -> C;
+        button_hovered_border() -> C;
+        button_pressed_bg() -> C;
+        button_pressed_text() -> C;
+        button_pressed_border() -> C;
+        button_disabled_bg() -> C;
+        button_disabled_text() -> C;
+        button_disabled_border() -> C;
+        button_border_width() -> u32;
+        button_pressed_offset() -> Point;
+        button_corner_radius() -> u32;
+        button_shadow_offset() -> Option<Point>;
+        button_bevel() -> bool;
+        button_gradient(state: WidgetState) -> Option<(Rgb888, Rgb888)>;
+        label_text_color() -> C;
+        label_disabled_text_color() -> C;
+        label_background_color() -> Option<C>;
+        panel_background_color() -> C;
+        panel_border_color() -> C;
+        panel_border_width() -> u32;
+        panel_corner_radius() -> u32;
+        panel_padding() -> u32;
+        textbox_background_color() -> C;
+        textbox_text_color() -> C;
+        textbox_border_color() -> C;
+        textbox_focused_border_color() -> C;
+        textbox_cursor_color() -> C;
+        textbox_selection_color() -> C;
+        textbox_border_width() -> u32;
+        textbox_padding() -> u32;
+        chart_background_color() -> C;
+        chart_grid_color() -> C;
+        chart_axis_text_color() -> C;
+        chart_series_color(index: usize) -> C;
+        meter_track_color() -> C;
+        meter_fill_color() -> C;
+        meter_border_color() -> C;
+        meter_needle_color() -> C;
+        meter_peak_color() -> C;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        i18n::{FontSet, StringId, Text},
+        themes::DefaultTheme,
+    };
+
+    static EN: Locale = Locale::new("en", &["Start"]);
+    static DE: Locale = Locale::new("de", &["Starten"]).with_fonts(&FontSet::LATIN_1);
+
+    #[test]
+    fn switching_locale_changes_strings_and_fonts() {
+        let mut theme = Localized::new(DefaultTheme::<Rgb888>::new(), &EN);
+        let start = Text::from(StringId(0));
+        assert_eq!(start.resolve(&theme), "Start");
+        assert_eq!(theme.normal_font(), theme.inner().normal_font());
+
+        theme.set_locale(&DE);
+        assert_eq!(start.resolve(&theme), "Starten");
+        assert_eq!(theme.normal_font(), FontSet::LATIN_1.normal);
+    }
+}
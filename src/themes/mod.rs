@@ -3,9 +3,26 @@ use embedded_graphics::mono_font::ascii::{FONT_4X6, FONT_6X10, FONT_8X13, FONT_1
 use embedded_graphics::pixelcolor::Rgb888;
 use embedded_graphics::prelude::*;
 
+/// Forward theme methods to the theme returned by `$source`
+macro_rules! forward {
+    ($source:ident => $($name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {
+        $(
+            fn $name(&self, $($arg: $ty),*) -> $ret {
+                self.$source().$name($($arg),*)
+            }
+        )*
+    };
+}
+
+pub mod accessible;
 pub mod colors;
+pub mod localized;
+pub mod style;
 pub mod theme;
 
+pub use accessible::{Accessible, HighContrastTheme, touch_target_size};
+pub use localized::Localized;
+pub use style::{ButtonKind, ResolvedStyle, StateColors, StateStyle, Style, WidgetKind};
 pub use theme::Theme;
 #[derive(Debug)]
 pub struct DefaultTheme<C: PixelColor> {
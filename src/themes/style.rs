@@ -0,0 +1,388 @@
+use embedded_graphics::{mono_font::MonoFont, prelude::*};
+use embedded_text::alignment::HorizontalAlignment;
+
+use crate::{StateFlags, Theme, WidgetState};
+
+/// One optional colour per [`WidgetState`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateColors<C> {
+    pub normal: Option<C>,
+    pub hovered: Option<C>,
+    pub pressed: Option<C>,
+    pub focused: Option<C>,
+    pub disabled: Option<C>,
+}
+
+impl<C: Copy> StateColors<C> {
+    /// No overrides, every state uses the theme
+    pub const fn new() -> Self {
+        Self {
+            normal: None,
+            hovered: None,
+            pressed: None,
+            focused: None,
+            disabled: None,
+        }
+    }
+    /// The same colour in every state except `Disabled`, which keeps
+    /// following the theme so disabled widgets still look disabled
+    pub const fn all(color: C) -> Self {
+        Self {
+            normal: Some(color),
+            hovered: Some(color),
+            pressed: Some(color),
+            focused: Some(color),
+            disabled: None,
+        }
+    }
+    pub const fn with(mut self, state: WidgetState, color: C) -> Self {
+        match state {
+            WidgetState::Normal => self.normal = Some(color),
+            WidgetState::Hovered => self.hovered = Some(color),
+            WidgetState::Pressed => self.pressed = Some(color),
+            WidgetState::Focused => self.focused = Some(color),
+            WidgetState::Disabled => self.disabled = Some(color),
+        }
+        self
+    }
+    pub fn get(&self, state: WidgetState) -> Option<C> {
+        match state {
+            WidgetState::Normal => self.normal,
+            WidgetState::Hovered => self.hovered,
+            WidgetState::Pressed => self.pressed,
+            WidgetState::Focused => self.focused,
+            WidgetState::Disabled => self.disabled,
+        }
+    }
+}
+
+impl<C: Copy> Default for StateColors<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Colours and font of a kind of widget in one [`WidgetState`], straight
+/// from the theme.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateStyle<C> {
+    pub background: C,
+    pub text: C,
+    pub border: C,
+    pub font: &'static MonoFont<'static>,
+}
+
+impl<C: PixelColor> StateStyle<C> {
+    /// Ask `kind` for its style in `state`, a [`WidgetState`] or a
+    /// combination of [`StateFlags`]
+    pub fn resolve<T, K>(theme: &T, kind: &K, state: impl Into<StateFlags>) -> Self
+    where
+        T: Theme<C>,
+        K: WidgetKind<C> + ?Sized,
+    {
+        kind.style(theme, state.into())
+    }
+}
+
+/// The theme hooks a kind of widget is drawn with.
+///
+/// Only [`normal`](WidgetKind::normal) is required, the other states are
+/// derived from it unless overridden. Combinations of flags go through
+/// [`style`](WidgetKind::style). A widget outside this crate gets
+/// state styling from its own theme colours like this:
+///
+/// ```
+/// # use embedded_graphics::{pixelcolor::Rgb888, prelude::*};
+/// # use embui::{Theme, WidgetState, themes::{DefaultTheme, StateStyle, Style, WidgetKind}};
+/// struct Meter;
+///
+/// impl<C: PixelColor> WidgetKind<C> for Meter {
+///     fn normal<T: Theme<C>>(&self, theme: &T) -> StateStyle<C> {
+///         StateStyle {
+///             background: theme.meter_track_color(),
+///             text: theme.text_primary(),
+///             border: theme.meter_border_color(),
+///             font: theme.small_font(),
+///         }
+///     }
+/// }
+///
+/// let theme = DefaultTheme::<Rgb888>::new();
+/// let style = Style::new().resolve_as(&theme, &Meter, WidgetState::Disabled);
+/// assert_eq!(style.background, theme.meter_track_color());
+/// assert_eq!(style.text, theme.text_disabled());
+/// ```
+pub trait WidgetKind<C: PixelColor> {
+    fn normal<T: Theme<C>>(&self, theme: &T) -> StateStyle<C>;
+    /// Normal with a light primary border
+    fn hovered<T: Theme<C>>(&self, theme: &T) -> StateStyle<C> {
+        StateStyle {
+            border: theme.primary_light(),
+            ..self.normal(theme)
+        }
+    }
+    /// Hovered with a dark primary border
+    fn pressed<T: Theme<C>>(&self, theme: &T) -> StateStyle<C> {
+        StateStyle {
+            border: theme.primary_dark(),
+            ..self.hovered(theme)
+        }
+    }
+    /// Normal with the focus ring colour as border
+    fn focused<T: Theme<C>>(&self, theme: &T) -> StateStyle<C> {
+        StateStyle {
+            border: theme.focus_ring_color(),
+            ..self.normal(theme)
+        }
+    }
+    /// Normal with disabled text and border
+    fn disabled<T: Theme<C>>(&self, theme: &T) -> StateStyle<C> {
+        StateStyle {
+            text: theme.text_disabled(),
+            border: theme.text_disabled(),
+            ..self.normal(theme)
+        }
+    }
+    /// The current entry of a group, like the active tab. Pressed by
+    /// default.
+    fn selected<T: Theme<C>>(&self, theme: &T) -> StateStyle<C> {
+        self.pressed(theme)
+    }
+    /// The style of the most prominent flag, with the focused border on
+    /// top while a focused widget is hovered or pressed. Selected widgets
+    /// look selected unless they are pressed or disabled.
+    fn style<T: Theme<C>>(&self, theme: &T, flags: StateFlags) -> StateStyle<C> {
+        let selected = flags.contains(StateFlags::SELECTED);
+        let style = match flags.state() {
+            WidgetState::Normal | WidgetState::Hovered | WidgetState::Focused if selected => {
+                self.selected(theme)
+            }
+            WidgetState::Normal => self.normal(theme),
+            WidgetState::Hovered => self.hovered(theme),
+            WidgetState::Pressed => self.pressed(theme),
+            WidgetState::Focused => return self.focused(theme),
+            WidgetState::Disabled => return self.disabled(theme),
+        };
+        if flags.contains(StateFlags::FOCUSED) {
+            StateStyle {
+                border: self.focused(theme).border,
+                ..style
+            }
+        } else {
+            style
+        }
+    }
+}
+
+/// The `button_*` theme hooks, also used by [`Number`](crate::widgets::Number).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ButtonKind;
+
+impl<C: PixelColor> WidgetKind<C> for ButtonKind {
+    fn normal<T: Theme<C>>(&self, theme: &T) -> StateStyle<C> {
+        StateStyle {
+            background: theme.button_normal_bg(),
+            text: theme.button_normal_text(),
+            border: theme.button_normal_border(),
+            font: theme.normal_font(),
+        }
+    }
+    fn hovered<T: Theme<C>>(&self, theme: &T) -> StateStyle<C> {
+        StateStyle {
+            background: theme.button_hovered_bg(),
+            text: theme.button_hovered_text(),
+            border: theme.button_hovered_border(),
+            font: theme.normal_font(),
+        }
+    }
+    fn pressed<T: Theme<C>>(&self, theme: &T) -> StateStyle<C> {
+        StateStyle {
+            background: theme.button_pressed_bg(),
+            text: theme.button_pressed_text(),
+            border: theme.button_pressed_border(),
+            font: theme.normal_font(),
+        }
+    }
+    fn focused<T: Theme<C>>(&self, theme: &T) -> StateStyle<C> {
+        StateStyle {
+            border: theme.primary_color(), // Different border for focus
+            ..self.hovered(theme)
+        }
+    }
+    fn disabled<T: Theme<C>>(&self, theme: &T) -> StateStyle<C> {
+        StateStyle {
+            background: theme.button_disabled_bg(),
+            text: theme.button_disabled_text(),
+            border: theme.button_disabled_border(),
+            font: theme.normal_font(),
+        }
+    }
+}
+
+/// Per-widget overrides on top of the global [`Theme`].
+///
+/// Every field left at `None` falls back to the theme, so a style only
+/// needs to name what is different:
+///
+/// ```
+/// # use embedded_graphics::{pixelcolor::Rgb888, prelude::*};
+/// # use embui::{themes::{StateColors, Style}, widgets::Button};
+/// # #[derive(Clone, Copy)]
+/// # enum Message { Delete }
+/// let delete: Button<Message, Rgb888> = Button::new("Delete")
+///     .on_press(Message::Delete)
+///     .with_style(
+///         Style::new()
+///             .with_background(StateColors::all(Rgb888::CSS_RED))
+///             .with_text(StateColors::all(Rgb888::CSS_WHITE)),
+///     );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Style<C> {
+    pub background: StateColors<C>,
+    pub text: StateColors<C>,
+    pub border: StateColors<C>,
+    pub font: Option<&'static MonoFont<'static>>,
+    pub padding: Option<u32>,
+    pub alignment: Option<HorizontalAlignment>,
+}
+
+/// A [`Style`] with the theme filled in for one [`WidgetState`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedStyle<C> {
+    pub background: C,
+    pub text: C,
+    pub border: C,
+    pub font: &'static MonoFont<'static>,
+    pub padding: u32,
+    pub alignment: HorizontalAlignment,
+}
+
+impl<C: PixelColor> Style<C> {
+    pub const fn new() -> Self {
+        Self {
+            background: StateColors::new(),
+            text: StateColors::new(),
+            border: StateColors::new(),
+            font: None,
+            padding: None,
+            alignment: None,
+        }
+    }
+
+    pub const fn with_background(mut self, colors: StateColors<C>) -> Self {
+        self.background = colors;
+        self
+    }
+    pub const fn with_text(mut self, colors: StateColors<C>) -> Self {
+        self.text = colors;
+        self
+    }
+    pub const fn with_border(mut self, colors: StateColors<C>) -> Self {
+        self.border = colors;
+        self
+    }
+    pub const fn with_font(mut self, font: &'static MonoFont<'static>) -> Self {
+        self.font = Some(font);
+        self
+    }
+    pub const fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+    pub const fn with_alignment(mut self, alignment: HorizontalAlignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Fill in everything this style leaves open from `theme`, with the
+    /// colours of a [`ButtonKind`]. Left and right alignment swap for
+    /// right-to-left locales. The overrides of the most prominent flag in
+    /// `state` apply.
+    pub fn resolve<T: Theme<C>>(
+        &self,
+        theme: &T,
+        state: impl Into<StateFlags>,
+    ) -> ResolvedStyle<C> {
+        self.resolve_as(theme, &ButtonKind, state)
+    }
+
+    /// Like [`Style::resolve`], with the theme hooks of `kind`
+    pub fn resolve_as<T, K>(
+        &self,
+        theme: &T,
+        kind: &K,
+        state: impl Into<StateFlags>,
+    ) -> ResolvedStyle<C>
+    where
+        T: Theme<C>,
+        K: WidgetKind<C>,
+    {
+        let flags = state.into();
+        let base = StateStyle::resolve(theme, kind, flags);
+        let state = flags.state();
+        ResolvedStyle {
+            background: self.background.get(state).unwrap_or(base.background),
+            text: self.text.get(state).unwrap_or(base.text),
+            border: self.border.get(state).unwrap_or(base.border),
+            font: self.font.unwrap_or(base.font),
+            padding: self.padding.unwrap_or_else(|| theme.spacing_xs()),
+            alignment: theme
+                .direction()
+                .align(self.alignment.unwrap_or(HorizontalAlignment::Center)),
+        }
+    }
+}
+
+impl<C: PixelColor> Default for Style<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::pixelcolor::Rgb888;
+
+    use super::*;
+    use crate::themes::DefaultTheme;
+
+    #[test]
+    fn unset_fields_fall_back_to_theme() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let style = Style::new()
+            .with_background(StateColors::all(Rgb888::CSS_RED))
+            .with_padding(0);
+
+        let normal = style.resolve(&theme, WidgetState::Normal);
+        assert_eq!(normal.background, Rgb888::CSS_RED);
+        assert_eq!(normal.text, theme.button_normal_text());
+        assert_eq!(normal.border, theme.button_normal_border());
+        assert_eq!(normal.padding, 0);
+        assert_eq!(normal.font, theme.normal_font());
+
+        let disabled = style.resolve(&theme, WidgetState::Disabled);
+        assert_eq!(disabled.background, theme.button_disabled_bg());
+    }
+
+    #[test]
+    fn focus_shows_while_pressed() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let pressed = StateStyle::resolve(&theme, &ButtonKind, WidgetState::Pressed);
+        let focused_pressed = StateStyle::resolve(
+            &theme,
+            &ButtonKind,
+            StateFlags::FOCUSED | StateFlags::PRESSED,
+        );
+        assert_eq!(focused_pressed.background, pressed.background);
+        assert_eq!(focused_pressed.border, theme.primary_color());
+
+        // Disabled wins over everything
+        let disabled = StateStyle::resolve(
+            &theme,
+            &ButtonKind,
+            StateFlags::DISABLED | StateFlags::FOCUSED,
+        );
+        assert_eq!(disabled.background, theme.button_disabled_bg());
+    }
+}
@@ -1,6 +1,12 @@
 use embedded_graphics::mono_font::MonoFont;
+use embedded_graphics::pixelcolor::Rgb888;
 use embedded_graphics::prelude::*;
 
+use crate::{
+    WidgetState,
+    i18n::{Direction, Locale},
+};
+
 pub trait Theme<C: PixelColor> {
     // Color palette access
     fn primary_color(&self) -> C;
@@ -23,6 +29,16 @@ pub trait Theme<C: PixelColor> {
     fn large_font(&self) -> &'static MonoFont<'static>;
     fn title_font(&self) -> &'static MonoFont<'static>;
 
+    // Localization
+    /// Strings for [`Text::Id`](crate::i18n::Text) labels
+    fn locale(&self) -> Option<&'static Locale> {
+        None
+    }
+    fn direction(&self) -> Direction {
+        self.locale()
+            .map_or(Direction::Ltr, |locale| locale.direction)
+    }
+
     // Spacing
     fn spacing_xs(&self) -> u32 {
         2
@@ -43,6 +59,26 @@ pub trait Theme<C: PixelColor> {
         32
     }
 
+    // Shape styling
+    fn shadow_color(&self) -> C {
+        self.border_color()
+    }
+    fn bevel_light_color(&self) -> C {
+        self.background_color()
+    }
+    fn bevel_dark_color(&self) -> C {
+        self.text_secondary()
+    }
+
+    /// Width of the ring drawn inside focused widgets, so focus shows
+    /// without relying on colour. 0 turns it off.
+    fn focus_ring_width(&self) -> u32 {
+        1
+    }
+    fn focus_ring_color(&self) -> C {
+        self.text_primary()
+    }
+
     // Button-specific styling
     fn button_normal_bg(&self) -> C {
         self.surface_color()
@@ -93,6 +129,19 @@ pub trait Theme<C: PixelColor> {
     fn button_corner_radius(&self) -> u32 {
         0
     }
+    /// Offset of the drop shadow, `None` draws no shadow
+    fn button_shadow_offset(&self) -> Option<Point> {
+        None
+    }
+    /// Draw a raised bevel that inverts when pressed
+    fn button_bevel(&self) -> bool {
+        false
+    }
+    /// Top and bottom colours of a vertical gradient replacing the
+    /// background colour, for RGB targets
+    fn button_gradient(&self, _state: WidgetState) -> Option<(Rgb888, Rgb888)> {
+        None
+    }
 
     // Label-specific styling
     fn label_text_color(&self) -> C {
@@ -115,6 +164,9 @@ pub trait Theme<C: PixelColor> {
     fn panel_border_width(&self) -> u32 {
         1
     }
+    fn panel_corner_radius(&self) -> u32 {
+        0
+    }
     fn panel_padding(&self) -> u32 {
         self.spacing_md()
     }
@@ -144,4 +196,40 @@ pub trait Theme<C: PixelColor> {
     fn textbox_padding(&self) -> u32 {
         self.spacing_sm()
     }
+
+    // Chart-specific styling
+    fn chart_background_color(&self) -> C {
+        self.panel_background_color()
+    }
+    fn chart_grid_color(&self) -> C {
+        self.border_color()
+    }
+    fn chart_axis_text_color(&self) -> C {
+        self.text_secondary()
+    }
+    fn chart_series_color(&self, index: usize) -> C {
+        match index % 4 {
+            0 => self.primary_color(),
+            1 => self.secondary_color(),
+            2 => self.success_color(),
+            _ => self.warning_color(),
+        }
+    }
+
+    // Meter-specific styling (progress bars, gauges, level meters)
+    fn meter_track_color(&self) -> C {
+        self.surface_color()
+    }
+    fn meter_fill_color(&self) -> C {
+        self.primary_color()
+    }
+    fn meter_border_color(&self) -> C {
+        self.border_color()
+    }
+    fn meter_needle_color(&self) -> C {
+        self.text_primary()
+    }
+    fn meter_peak_color(&self) -> C {
+        self.text_primary()
+    }
 }
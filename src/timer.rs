@@ -0,0 +1,234 @@
+//! Timers driven by a monotonic millisecond count.
+//!
+//! Nothing here reads a hardware clock: every call takes the current time
+//! as `now`, so the same code runs on a microcontroller tick counter, a
+//! desktop clock or a fake clock in tests.
+use heapless::Vec;
+
+/// Handle to a timer in [`Timers`], used to cancel it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimerId(u32);
+
+#[derive(Debug, Clone, Copy)]
+struct Timer<M> {
+    id: TimerId,
+    due: u64,
+    period: Option<u32>,
+    message: M,
+}
+
+/// Up to `N` one-shot and repeating timers, each producing a message.
+#[derive(Debug, Clone)]
+pub struct Timers<M, const N: usize> {
+    timers: Vec<Timer<M>, N>,
+    next_id: u32,
+}
+
+impl<M: Copy, const N: usize> Timers<M, N> {
+    pub const fn new() -> Self {
+        Self {
+            timers: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Send `message` once, `ms` milliseconds after `now`. Returns `None`
+    /// if all `N` timers are in use.
+    pub fn after(&mut self, now: u64, ms: u32, message: M) -> Option<TimerId> {
+        self.add(now + ms as u64, None, message)
+    }
+
+    /// Send `message` every `ms` milliseconds, starting `ms` after `now`
+    pub fn every(&mut self, now: u64, ms: u32, message: M) -> Option<TimerId> {
+        let period = ms.max(1);
+        self.add(now + period as u64, Some(period), message)
+    }
+
+    fn add(&mut self, due: u64, period: Option<u32>, message: M) -> Option<TimerId> {
+        let id = TimerId(self.next_id);
+        self.timers
+            .push(Timer {
+                id,
+                due,
+                period,
+                message,
+            })
+            .ok()?;
+        self.next_id = self.next_id.wrapping_add(1);
+        Some(id)
+    }
+
+    /// Stop a timer. Returns false if it already fired or was cancelled.
+    pub fn cancel(&mut self, id: TimerId) -> bool {
+        let len = self.timers.len();
+        self.timers.retain(|timer| timer.id != id);
+        self.timers.len() != len
+    }
+    pub fn clear(&mut self) {
+        self.timers.clear();
+    }
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+    /// When the next timer is due
+    pub fn next_due(&self) -> Option<u64> {
+        self.timers.iter().map(|timer| timer.due).min()
+    }
+
+    /// Hand every message that is due at `now` to `f`. A repeating timer
+    /// that fell behind fires once and skips the periods it missed.
+    pub fn poll(&mut self, now: u64, mut f: impl FnMut(M)) {
+        for timer in self.timers.iter_mut().filter(|timer| timer.due <= now) {
+            f(timer.message);
+            if let Some(period) = timer.period {
+                let missed = (now - timer.due) / period as u64;
+                timer.due += (missed + 1) * period as u64;
+            }
+        }
+        self.timers
+            .retain(|timer| timer.due > now || timer.period.is_some());
+    }
+}
+
+impl<M: Copy, const N: usize> Default for Timers<M, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sends a message once no input arrived for a while, e.g. to dim the
+/// backlight or return to the home screen.
+#[derive(Debug, Clone, Copy)]
+pub struct IdleTimeout<M> {
+    timeout: u32,
+    message: M,
+    last_activity: u64,
+    fired: bool,
+}
+
+impl<M: Copy> IdleTimeout<M> {
+    pub const fn new(timeout: u32, message: M) -> Self {
+        Self {
+            timeout,
+            message,
+            last_activity: 0,
+            fired: false,
+        }
+    }
+    /// Restart the countdown
+    pub fn activity(&mut self, now: u64) {
+        self.last_activity = now;
+        self.fired = false;
+    }
+    /// The message, once per idle period
+    pub fn poll(&mut self, now: u64) -> Option<M> {
+        if self.fired || now < self.last_activity + self.timeout as u64 {
+            return None;
+        }
+        self.fired = true;
+        Some(self.message)
+    }
+    pub fn is_idle(&self) -> bool {
+        self.fired
+    }
+    /// When the message will be sent, unless there is input first
+    pub fn deadline(&self) -> Option<u64> {
+        (!self.fired).then_some(self.last_activity + self.timeout as u64)
+    }
+}
+
+/// Blink phase for text cursors.
+///
+/// The cursor stays visible for a full period after [`Blink::restart`], so
+/// it doesn't vanish while the user is typing.
+#[derive(Debug, Clone, Copy)]
+pub struct Blink {
+    period: u32,
+    start: u64,
+    visible: bool,
+}
+
+impl Blink {
+    /// Toggle every `period` milliseconds
+    pub const fn new(period: u32) -> Self {
+        Self {
+            period: if period == 0 { 1 } else { period },
+            start: 0,
+            visible: true,
+        }
+    }
+    pub fn restart(&mut self, now: u64) {
+        self.start = now;
+        self.visible = true;
+    }
+    /// Advance to `now`. Returns true if the cursor toggled.
+    pub fn update(&mut self, now: u64) -> bool {
+        let phase = now.saturating_sub(self.start) / self.period as u64;
+        let visible = phase.is_multiple_of(2);
+        let toggled = visible != self.visible;
+        self.visible = visible;
+        toggled
+    }
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+    /// When the cursor toggles next, as seen from `now`
+    pub fn next_toggle(&self, now: u64) -> u64 {
+        let period = self.period as u64;
+        let phase = now.saturating_sub(self.start) / period;
+        self.start + (phase + 1) * period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timers_fire_and_cancel() {
+        let mut timers = Timers::<char, 4>::new();
+        let once = timers.after(0, 50, 'a').unwrap();
+        let repeat = timers.every(0, 100, 'b').unwrap();
+        let mut fired = heapless::String::<8>::new();
+
+        timers.poll(49, |m| fired.push(m).unwrap());
+        timers.poll(100, |m| fired.push(m).unwrap());
+        assert_eq!(fired, "ab");
+        assert!(!timers.cancel(once));
+
+        // Falling behind fires once, then stays on the original grid
+        timers.poll(350, |m| fired.push(m).unwrap());
+        timers.poll(399, |m| fired.push(m).unwrap());
+        timers.poll(400, |m| fired.push(m).unwrap());
+        assert_eq!(fired, "abbb");
+
+        assert!(timers.cancel(repeat));
+        assert!(timers.is_empty());
+    }
+
+    #[test]
+    fn idle_timeout_fires_once_per_idle_period() {
+        let mut idle = IdleTimeout::new(1000, ());
+        assert_eq!(idle.poll(999), None);
+        assert_eq!(idle.poll(1000), Some(()));
+        assert_eq!(idle.poll(5000), None);
+        idle.activity(5000);
+        assert!(!idle.is_idle());
+        assert_eq!(idle.poll(6000), Some(()));
+    }
+
+    #[test]
+    fn blink_restarts_visible() {
+        let mut blink = Blink::new(500);
+        assert!(!blink.update(499));
+        assert!(blink.update(500));
+        assert!(!blink.is_visible());
+        blink.restart(600);
+        assert!(blink.is_visible());
+        assert!(!blink.update(1099));
+        assert!(blink.update(1100));
+    }
+}
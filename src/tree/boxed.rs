@@ -0,0 +1,536 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::any::Any;
+
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*};
+
+use super::NodeId;
+use crate::{InputEvent, Theme, WidgetId, screen::Element};
+
+type BoxedElement<'a, M, D, T, C> = Box<dyn Element<M, D, T, C> + 'a>;
+
+/// An element that can be looked up by its type
+trait AnyElement<M, D, T, C>: Element<M, D, T, C> + Any
+where
+    M: Copy,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    C: PixelColor + Default + From<Rgb888>,
+{
+}
+
+impl<X, M, D, T, C> AnyElement<M, D, T, C> for X
+where
+    X: Element<M, D, T, C> + Any,
+    M: Copy,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    C: PixelColor + Default + From<Rgb888>,
+{
+}
+
+enum Boxed<'a, M, D, T, C> {
+    Borrowing(BoxedElement<'a, M, D, T, C>),
+    Typed(Box<dyn AnyElement<M, D, T, C>>),
+}
+
+impl<'a, M, D, T, C> Boxed<'a, M, D, T, C>
+where
+    M: Copy,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    C: PixelColor + Default + From<Rgb888>,
+{
+    fn element(&self) -> &(dyn Element<M, D, T, C> + 'a) {
+        match self {
+            Self::Borrowing(widget) => &**widget,
+            Self::Typed(widget) => &**widget,
+        }
+    }
+    fn element_mut(&mut self) -> &mut (dyn Element<M, D, T, C> + 'a) {
+        match self {
+            Self::Borrowing(widget) => &mut **widget,
+            Self::Typed(widget) => &mut **widget,
+        }
+    }
+    fn into_element(self) -> BoxedElement<'a, M, D, T, C> {
+        match self {
+            Self::Borrowing(widget) => widget,
+            Self::Typed(widget) => widget,
+        }
+    }
+    fn as_any(&self) -> Option<&dyn Any> {
+        match self {
+            Self::Borrowing(_) => None,
+            Self::Typed(widget) => Some(&**widget),
+        }
+    }
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        match self {
+            Self::Borrowing(_) => None,
+            Self::Typed(widget) => Some(&mut **widget),
+        }
+    }
+}
+
+struct Node<'a, M, D, T, C> {
+    widget: Boxed<'a, M, D, T, C>,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+struct Slot<'a, M, D, T, C> {
+    generation: u16,
+    node: Option<Node<'a, M, D, T, C>>,
+}
+
+/// A tree of boxed widgets of any type that grows and shrinks at runtime.
+///
+/// Same API as [`Arena`](super::Arena), plus [`Tree::remove`]. The draw
+/// target and theme are part of the type because the widgets are trait
+/// objects. Widgets added with [`Tree::insert_typed`] or
+/// [`Tree::insert_child_typed`] must not borrow anything, and can be looked
+/// up by type with [`Tree::widget`].
+pub struct Tree<'a, M, D, T, C> {
+    slots: Vec<Slot<'a, M, D, T, C>>,
+    roots: Vec<NodeId>,
+    len: usize,
+}
+
+impl<'a, M, D, T, C> Tree<'a, M, D, T, C>
+where
+    M: Copy,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    C: PixelColor + Default + From<Rgb888>,
+{
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            roots: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Add a widget without a parent.
+    ///
+    /// # Panics
+    ///
+    /// If the tree already holds 65,536 widgets.
+    pub fn insert(&mut self, widget: impl Element<M, D, T, C> + 'a) -> NodeId {
+        self.insert_boxed(None, Boxed::Borrowing(Box::new(widget)))
+    }
+    /// Add a widget without a parent that [`Tree::widget`] can find.
+    ///
+    /// # Panics
+    ///
+    /// If the tree already holds 65,536 widgets.
+    pub fn insert_typed(&mut self, widget: impl Element<M, D, T, C> + 'static) -> NodeId {
+        self.insert_boxed(None, Boxed::Typed(Box::new(widget)))
+    }
+
+    /// Add a widget as the last child of `parent`, hidden if `parent` is
+    /// hidden or shows another [child](crate::Widget::active_child).
+    ///
+    /// # Panics
+    ///
+    /// If `parent` has been removed, or the tree already holds 65,536
+    /// widgets.
+    pub fn insert_child(
+        &mut self,
+        parent: NodeId,
+        widget: impl Element<M, D, T, C> + 'a,
+    ) -> NodeId {
+        self.insert_boxed(Some(parent), Boxed::Borrowing(Box::new(widget)))
+    }
+    /// Add a widget as the last child of `parent` that [`Tree::widget`]
+    /// can find, see [`Tree::insert_child`].
+    ///
+    /// # Panics
+    ///
+    /// If `parent` has been removed, or the tree already holds 65,536
+    /// widgets.
+    pub fn insert_child_typed(
+        &mut self,
+        parent: NodeId,
+        widget: impl Element<M, D, T, C> + 'static,
+    ) -> NodeId {
+        self.insert_boxed(Some(parent), Boxed::Typed(Box::new(widget)))
+    }
+
+    fn insert_boxed(&mut self, parent: Option<NodeId>, widget: Boxed<'a, M, D, T, C>) -> NodeId {
+        let Some(parent) = parent else {
+            let id = self.push(widget, None);
+            self.roots.push(id);
+            return id;
+        };
+        assert!(self.contains(parent), "parent widget was removed");
+        let id = self.push(widget, Some(parent));
+        let parent = self.node_mut(parent).unwrap();
+        let widget = parent.widget.element();
+        let shown = widget.is_visible()
+            && widget
+                .active_child()
+                .is_none_or(|active| active == parent.children.len());
+        parent.children.push(id);
+        if !shown {
+            self.get_mut(id).unwrap().set_visible(false);
+        }
+        id
+    }
+
+    fn push(&mut self, widget: Boxed<'a, M, D, T, C>, parent: Option<NodeId>) -> NodeId {
+        let node = Node {
+            widget,
+            parent,
+            children: Vec::new(),
+        };
+        self.len += 1;
+        match self.slots.iter().position(|slot| slot.node.is_none()) {
+            Some(index) => {
+                let slot = &mut self.slots[index];
+                slot.node = Some(node);
+                NodeId {
+                    index: index as u16,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                // Wrapping would alias the ids of older widgets
+                let index = u16::try_from(self.slots.len()).expect("too many widgets in the tree");
+                self.slots.push(Slot {
+                    generation: 0,
+                    node: Some(node),
+                });
+                NodeId {
+                    index,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// Remove a widget and all its descendants, returning the widget
+    pub fn remove(&mut self, id: NodeId) -> Option<BoxedElement<'a, M, D, T, C>> {
+        let node = self.take(id)?;
+        match node.parent.and_then(|parent| self.node_mut(parent)) {
+            Some(parent) => parent.children.retain(|&child| child != id),
+            None => self.roots.retain(|&root| root != id),
+        }
+        let mut orphans = node.children;
+        while let Some(child) = orphans.pop() {
+            if let Some(child) = self.take(child) {
+                orphans.extend(child.children);
+            }
+        }
+        Some(node.widget.into_element())
+    }
+
+    fn take(&mut self, id: NodeId) -> Option<Node<'a, M, D, T, C>> {
+        let slot = self
+            .slots
+            .get_mut(id.index())
+            .filter(|slot| slot.generation == id.generation)?;
+        let node = slot.node.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.len -= 1;
+        Some(node)
+    }
+
+    fn node(&self, id: NodeId) -> Option<&Node<'a, M, D, T, C>> {
+        self.slots
+            .get(id.index())
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.node.as_ref())
+    }
+    fn node_mut(&mut self, id: NodeId) -> Option<&mut Node<'a, M, D, T, C>> {
+        self.slots
+            .get_mut(id.index())
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.node.as_mut())
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.node(id).is_some()
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&(dyn Element<M, D, T, C> + 'a)> {
+        self.node(id).map(|node| node.widget.element())
+    }
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut (dyn Element<M, D, T, C> + 'a)> {
+        self.node_mut(id).map(|node| node.widget.element_mut())
+    }
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.node(id).and_then(|node| node.parent)
+    }
+    /// The node of the widget with id `id`
+    pub fn find(&self, id: WidgetId) -> Option<NodeId> {
+        self.order()
+            .into_iter()
+            .find(|&node| self.get(node).is_some_and(|widget| widget.id() == Some(id)))
+    }
+    /// The widget with id `id`, if it was added with [`Tree::insert_typed`]
+    /// or [`Tree::insert_child_typed`] and is an `X`:
+    /// `tree.widget_mut::<Number<Message>>(COUNT)`
+    pub fn widget<X: 'static>(&self, id: WidgetId) -> Option<&X> {
+        self.node(self.find(id)?)?.widget.as_any()?.downcast_ref()
+    }
+    pub fn widget_mut<X: 'static>(&mut self, id: WidgetId) -> Option<&mut X> {
+        let node = self.find(id)?;
+        self.node_mut(node)?.widget.as_any_mut()?.downcast_mut()
+    }
+    /// The children of `id`, in insertion order
+    pub fn children(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.node(id)
+            .map_or(&[][..], |node| &node.children)
+            .iter()
+            .copied()
+    }
+
+    /// Every id, parents before children and siblings in insertion order
+    fn order(&self) -> Vec<NodeId> {
+        let mut order = Vec::with_capacity(self.len);
+        let mut stack: Vec<NodeId> = self.roots.iter().rev().copied().collect();
+        while let Some(id) = stack.pop() {
+            order.push(id);
+            if let Some(node) = self.node(id) {
+                stack.extend(node.children.iter().rev());
+            }
+        }
+        order
+    }
+
+    /// Every widget, parents before children
+    pub fn iter(&self) -> impl Iterator<Item = &(dyn Element<M, D, T, C> + 'a)> {
+        self.order().into_iter().filter_map(|id| self.get(id))
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut (dyn Element<M, D, T, C> + 'a)> {
+        self.elements().into_iter()
+    }
+
+    /// Every widget, for [`Application::view`](crate::Application::view)
+    pub fn elements(&mut self) -> Vec<&mut (dyn Element<M, D, T, C> + 'a)> {
+        let order = self.order();
+        let mut widgets: Vec<_> = self
+            .slots
+            .iter_mut()
+            .map(|slot| slot.node.as_mut().map(|node| node.widget.element_mut()))
+            .collect();
+        order
+            .into_iter()
+            .filter_map(|id| widgets[id.index()].take())
+            .collect()
+    }
+
+    /// Draw every visible widget, parents first
+    pub fn draw(&self, target: &mut D, theme: &T) -> Result<(), D::Error> {
+        for widget in self.iter().filter(|widget| widget.is_visible()) {
+            widget.draw_with_theme(target, theme)?;
+        }
+        for widget in self.iter().filter(|widget| widget.is_visible()) {
+            widget.draw_overlay(target, theme)?;
+        }
+        Ok(())
+    }
+
+    /// The visible widget at `point` drawn last, usually the innermost one.
+    /// While a widget has an open [overlay](crate::Widget::overlay), it is
+    /// the target of every point.
+    pub fn target(&self, point: Point) -> Option<NodeId> {
+        let shown: Vec<_> = self
+            .order()
+            .into_iter()
+            .rev()
+            .filter_map(|id| Some(id).zip(self.get(id)))
+            .filter(|(_, widget)| widget.is_visible())
+            .collect();
+        shown
+            .iter()
+            .find(|(_, widget)| widget.overlay().is_some())
+            .or_else(|| {
+                shown
+                    .iter()
+                    .find(|(_, widget)| widget.bounding_box().contains(point))
+            })
+            .map(|&(id, _)| id)
+    }
+
+    /// Hand `event` to `target` and the widgets around it, see
+    /// [`Arena::dispatch`](super::Arena::dispatch)
+    pub fn dispatch(
+        &mut self,
+        target: NodeId,
+        event: InputEvent,
+        mut sent: impl FnMut(M, Option<WidgetId>),
+    ) -> bool {
+        let mut path = Vec::new();
+        let mut node = Some(target).filter(|&id| self.contains(id));
+        while let Some(id) = node {
+            path.push(id);
+            node = self.parent(id);
+        }
+        let mut visit = |tree: &mut Self, id: NodeId, capture: bool| {
+            let Some(widget) = tree.get_mut(id).filter(|widget| widget.is_visible()) else {
+                return false;
+            };
+            let response = if capture {
+                widget.capture_event(event)
+            } else {
+                widget.handle_event(event)
+            };
+            if let Some(message) = response.message() {
+                sent(message, widget.id());
+            }
+            response.is_consumed()
+        };
+        let consumed = path.iter().skip(1).rev().any(|&id| visit(self, id, true))
+            || path.iter().any(|&id| visit(self, id, false));
+        for &id in path.iter().rev() {
+            let active = self
+                .get(id)
+                .filter(|widget| widget.is_visible())
+                .and_then(|widget| widget.active_child());
+            if let Some(active) = active {
+                self.show_child(id, active);
+            }
+        }
+        consumed
+    }
+
+    /// Enable or disable `node` and everything inside it
+    pub fn set_enabled(&mut self, node: NodeId, enabled: bool) {
+        self.for_subtree(node, |widget| {
+            widget.set_enabled(enabled);
+        });
+    }
+    /// Show or hide `node` and everything inside it, except the pages
+    /// inside that their paged containers don't show
+    pub fn set_visible(&mut self, node: NodeId, visible: bool) {
+        let mut stack = alloc::vec![(node, visible)];
+        while let Some((id, visible)) = stack.pop() {
+            if let Some(node) = self.node_mut(id) {
+                let widget = node.widget.element_mut();
+                widget.set_visible(visible);
+                let active = widget.active_child();
+                let children = node.children.iter().enumerate();
+                stack.extend(children.map(|(index, &child)| {
+                    (
+                        child,
+                        visible && active.is_none_or(|active| active == index),
+                    )
+                }));
+            }
+        }
+    }
+
+    /// Show the `index`th child of `parent` and hide its siblings, for
+    /// paged containers like [`Tabs`](crate::widgets::Tabs)
+    pub fn show_child(&mut self, parent: NodeId, index: usize) {
+        let children: Vec<NodeId> = self.children(parent).collect();
+        for (i, child) in children.into_iter().enumerate() {
+            self.set_visible(child, i == index);
+        }
+    }
+
+    fn for_subtree(&mut self, node: NodeId, mut f: impl FnMut(&mut dyn Element<M, D, T, C>)) {
+        let mut stack = alloc::vec![node];
+        while let Some(id) = stack.pop() {
+            if let Some(node) = self.node_mut(id) {
+                f(node.widget.element_mut());
+                stack.extend(node.children.iter().copied());
+            }
+        }
+    }
+}
+
+impl<M, D, T, C> Default for Tree<'_, M, D, T, C>
+where
+    M: Copy,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    C: PixelColor + Default + From<Rgb888>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{mock_display::MockDisplay, primitives::Rectangle};
+
+    use super::*;
+    use crate::{
+        InputEvent, Widget,
+        themes::DefaultTheme,
+        widgets::{Number, Tabs},
+    };
+
+    type Devices<'a> = Tree<'a, (), MockDisplay<Rgb888>, DefaultTheme<Rgb888>, Rgb888>;
+
+    fn device(y: i32) -> Number<'static, ()> {
+        Number::new(Point::new(0, y), Size::new(20, 10))
+    }
+
+    #[test]
+    fn removing_drops_the_subtree() {
+        let mut tree = Devices::new();
+        let list = tree.insert(device(0));
+        let first = tree.insert_child(list, device(10));
+        let second = tree.insert_child(list, device(20));
+        let detail = tree.insert_child(first, device(30));
+        assert_eq!(tree.len(), 4);
+
+        assert!(tree.remove(first).is_some());
+        assert_eq!(tree.len(), 2);
+        assert!(!tree.contains(detail));
+        assert!(tree.children(list).eq([second]));
+
+        // The freed slot is reused, the old id stays invalid
+        let third = tree.insert_child(list, device(10));
+        assert!(tree.get(first).is_none());
+        assert!(tree.children(list).eq([second, third]));
+        let order: Vec<_> = tree.iter().map(|w| w.bounding_box().top_left.y).collect();
+        assert_eq!(order, [0, 20, 10]);
+    }
+
+    #[test]
+    fn typed_widgets_are_found_by_id() {
+        const FIRST: WidgetId = WidgetId(1);
+        const SECOND: WidgetId = WidgetId(2);
+        let mut tree = Devices::new();
+        let list = tree.insert_typed(device(0).with_id(FIRST));
+        tree.insert_child(list, device(10).with_id(SECOND));
+
+        assert!(tree.widget::<Tabs<(), Rgb888, 2>>(FIRST).is_none());
+        let first = tree.widget_mut::<Number<()>>(FIRST).unwrap();
+        assert_eq!(first.bounding_box().top_left.y, 0);
+        // Only found as an element
+        assert!(tree.widget::<Number<()>>(SECOND).is_none());
+        assert!(tree.find(SECOND).is_some());
+    }
+
+    #[test]
+    fn tabs_show_only_the_active_page() {
+        let mut tree = Devices::new();
+        let tabs: Tabs<(), Rgb888, 2> =
+            Tabs::new(Rectangle::new(Point::zero(), Size::new(40, 40)), ["A", "B"]);
+        let header = tabs.header(1).center();
+        let tabs = tree.insert(tabs);
+        let [a, b] = [20, 30].map(|y| tree.insert_child(tabs, device(y)));
+        let shown = |tree: &Devices| [a, b].map(|page| tree.get(page).unwrap().is_visible());
+        assert_eq!(shown(&tree), [true, false]);
+
+        tree.dispatch(tabs, InputEvent::Touch(header), |_, _| {});
+        assert_eq!(shown(&tree), [false, true]);
+
+        tree.set_visible(tabs, false);
+        tree.set_visible(tabs, true);
+        assert_eq!(shown(&tree), [false, true]);
+    }
+}
@@ -0,0 +1,853 @@
+//! Widget trees that own their widgets, without `alloc`.
+//!
+//! [`widget_enum!`](crate::widget_enum) generates one enum over every widget
+//! type an application uses. The enum is a widget itself and an
+//! [`Element`] for any draw target and theme, so those are chosen when
+//! drawing rather than baked into the type. An [`Arena`] of that enum
+//! holds a fixed number of widgets linked to their parents and children by
+//! index; it can be created in a `const` context and placed in a `static`.
+//!
+//! With the `alloc` feature, `boxed::Tree` offers the same API for boxed
+//! widgets of any type, and widgets can also be removed.
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*};
+use heapless::Vec;
+
+use crate::{
+    InputEvent, Theme, Widget, WidgetId,
+    layout::{Hidden, Place, split},
+    screen::Element,
+    widgets::Orientation,
+};
+
+#[cfg(feature = "alloc")]
+pub mod boxed;
+
+/// Index of a widget in an [`Arena`] or a `boxed::Tree`.
+///
+/// Ids of removed widgets are not valid for the widgets that later reuse
+/// their slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId {
+    index: u16,
+    generation: u16,
+}
+
+impl NodeId {
+    pub fn index(self) -> usize {
+        self.index as usize
+    }
+}
+
+/// Typed access to the widgets inside a [`widget_enum!`](crate::widget_enum)
+/// enum, implemented for each of its widget types.
+pub trait Variant<W> {
+    /// The message type of the enum, so lookups need no annotation for it
+    type Message: Copy;
+
+    fn as_variant(&self) -> Option<&W>;
+    fn as_variant_mut(&mut self) -> Option<&mut W>;
+}
+
+#[derive(Debug, Clone)]
+struct Node<W> {
+    widget: W,
+    parent: Option<NodeId>,
+    first_child: Option<NodeId>,
+    last_child: Option<NodeId>,
+    next_sibling: Option<NodeId>,
+}
+
+/// Up to `N` widgets of type `W`, usually an enum from
+/// [`widget_enum!`](crate::widget_enum), arranged in a tree.
+///
+/// Widgets are kept in insertion order, so parents come before their
+/// children when iterating and drawing. Nodes are never removed; hide
+/// widgets that are not needed instead. `N` is at most 65,536, like the
+/// widgets a `boxed::Tree` can hold.
+#[derive(Debug, Clone)]
+pub struct Arena<W, const N: usize> {
+    nodes: Vec<Node<W>, N>,
+}
+
+impl<W, const N: usize> Arena<W, N> {
+    pub const fn new() -> Self {
+        // Node indices must fit in a `NodeId` without wrapping
+        const { assert!(N <= 1 << 16, "an arena holds at most 65,536 widgets") };
+        Self { nodes: Vec::new() }
+    }
+
+    /// Add a widget without a parent. Gives the widget back when the arena
+    /// is full.
+    pub fn insert(&mut self, widget: impl Into<W>) -> Result<NodeId, W> {
+        self.push(widget.into(), None)
+    }
+
+    /// Add a widget as the last child of `parent`, hidden if `parent` is
+    /// hidden or shows another [child](Widget::active_child). Gives the
+    /// widget back when the arena is full or `parent` is not a node of this
+    /// arena.
+    pub fn insert_child<M: Copy>(
+        &mut self,
+        parent: NodeId,
+        widget: impl Into<W>,
+    ) -> Result<NodeId, W>
+    where
+        W: Widget<M>,
+    {
+        let widget = widget.into();
+        // Arena ids never have a later generation
+        if parent.index() >= self.nodes.len() || parent.generation != 0 {
+            return Err(widget);
+        }
+        let id = self.push(widget, Some(parent))?;
+        let position = self.children(parent).count();
+        let parent = &mut self.nodes[parent.index()];
+        let shown = parent.widget.is_visible()
+            && parent
+                .widget
+                .active_child()
+                .is_none_or(|active| active == position);
+        match parent.last_child.replace(id) {
+            Some(last) => self.nodes[last.index()].next_sibling = Some(id),
+            None => parent.first_child = Some(id),
+        }
+        if !shown {
+            self.nodes[id.index()].widget.set_visible(false);
+        }
+        Ok(id)
+    }
+
+    fn push(&mut self, widget: W, parent: Option<NodeId>) -> Result<NodeId, W> {
+        let id = NodeId {
+            index: self.nodes.len() as u16,
+            generation: 0,
+        };
+        self.nodes
+            .push(Node {
+                widget,
+                parent,
+                first_child: None,
+                last_child: None,
+                next_sibling: None,
+            })
+            .map_err(|node| node.widget)?;
+        Ok(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&W> {
+        self.nodes.get(id.index()).map(|node| &node.widget)
+    }
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut W> {
+        self.nodes.get_mut(id.index()).map(|node| &mut node.widget)
+    }
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes.get(id.index()).and_then(|node| node.parent)
+    }
+    /// The node of the widget with id `id`
+    pub fn find<M: Copy>(&self, id: WidgetId) -> Option<NodeId>
+    where
+        W: Widget<M>,
+    {
+        self.nodes
+            .iter()
+            .position(|node| node.widget.id() == Some(id))
+            .map(|index| NodeId {
+                index: index as u16,
+                generation: 0,
+            })
+    }
+    /// The widget with id `id`, if it is an `X`:
+    /// `arena.widget_mut::<Number<Message>>(COUNT)`
+    pub fn widget<X>(&self, id: WidgetId) -> Option<&X>
+    where
+        W: Variant<X> + Widget<<W as Variant<X>>::Message>,
+    {
+        self.get(self.find(id)?)?.as_variant()
+    }
+    pub fn widget_mut<X>(&mut self, id: WidgetId) -> Option<&mut X>
+    where
+        W: Variant<X> + Widget<<W as Variant<X>>::Message>,
+    {
+        let node = self.find(id)?;
+        self.get_mut(node)?.as_variant_mut()
+    }
+    /// The children of `id`, in insertion order
+    pub fn children(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let first = self.nodes.get(id.index()).and_then(|node| node.first_child);
+        core::iter::successors(first, |child| self.nodes[child.index()].next_sibling)
+    }
+
+    /// Every widget, parents before children
+    pub fn iter(&self) -> impl Iterator<Item = &W> {
+        self.nodes.iter().map(|node| &node.widget)
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut W> {
+        self.nodes.iter_mut().map(|node| &mut node.widget)
+    }
+
+    /// Every widget as an element, for
+    /// [`Application::view`](crate::Application::view)
+    pub fn elements<M, D, T, C>(&mut self) -> Vec<&mut dyn Element<M, D, T, C>, N>
+    where
+        W: Element<M, D, T, C>,
+        M: Copy,
+        D: DrawTarget<Color = C>,
+        T: Theme<C>,
+        C: PixelColor + Default + From<Rgb888>,
+    {
+        self.nodes
+            .iter_mut()
+            .map(|node| &mut node.widget as &mut dyn Element<M, D, T, C>)
+            .collect()
+    }
+
+    /// Draw every visible widget, parents first
+    pub fn draw<M, D, T, C>(&self, target: &mut D, theme: &T) -> Result<(), D::Error>
+    where
+        W: Element<M, D, T, C>,
+        M: Copy,
+        D: DrawTarget<Color = C>,
+        T: Theme<C>,
+        C: PixelColor + Default + From<Rgb888>,
+    {
+        for widget in self.iter().filter(|widget| widget.is_visible()) {
+            widget.draw_with_theme(target, theme)?;
+        }
+        for widget in self.iter().filter(|widget| widget.is_visible()) {
+            widget.draw_overlay(target, theme)?;
+        }
+        Ok(())
+    }
+
+    /// Enable or disable `node` and everything inside it
+    pub fn set_enabled<M: Copy>(&mut self, node: NodeId, enabled: bool)
+    where
+        W: Widget<M>,
+    {
+        self.for_subtree(node, |widget| {
+            widget.set_enabled(enabled);
+        });
+    }
+    /// Show or hide `node` and everything inside it, except the pages
+    /// inside that their paged containers don't show
+    pub fn set_visible<M: Copy>(&mut self, node: NodeId, visible: bool)
+    where
+        W: Widget<M>,
+    {
+        if node.index() >= self.nodes.len() {
+            return;
+        }
+        for index in node.index()..self.nodes.len() {
+            if index == node.index() || self.is_inside(index, node) {
+                let shown = visible && self.is_shown(index, node);
+                self.nodes[index].widget.set_visible(shown);
+            }
+        }
+    }
+
+    /// Show the `index`th child of `parent` and hide its siblings, for
+    /// paged containers like [`Tabs`](crate::widgets::Tabs)
+    pub fn show_child<M: Copy>(&mut self, parent: NodeId, index: usize)
+    where
+        W: Widget<M>,
+    {
+        let children: Vec<NodeId, N> = self.children(parent).collect();
+        for (i, child) in children.into_iter().enumerate() {
+            self.set_visible(child, i == index);
+        }
+    }
+
+    fn for_subtree(&mut self, node: NodeId, mut f: impl FnMut(&mut W)) {
+        if node.index() >= self.nodes.len() {
+            return;
+        }
+        // Descendants always come after their ancestors
+        for index in node.index()..self.nodes.len() {
+            if index == node.index() || self.is_inside(index, node) {
+                f(&mut self.nodes[index].widget);
+            }
+        }
+    }
+    /// Whether every paged container between `ancestor` and the widget at
+    /// `index` shows the page it is on
+    fn is_shown<M: Copy>(&self, index: usize, ancestor: NodeId) -> bool
+    where
+        W: Widget<M>,
+    {
+        let mut child = NodeId {
+            index: index as u16,
+            generation: 0,
+        };
+        while child != ancestor
+            && let Some(parent) = self.nodes[child.index()].parent
+        {
+            if let Some(active) = self.nodes[parent.index()].widget.active_child()
+                && self.children(parent).nth(active) != Some(child)
+            {
+                return false;
+            }
+            child = parent;
+        }
+        true
+    }
+    fn is_inside(&self, index: usize, ancestor: NodeId) -> bool {
+        let mut parent = self.nodes[index].parent;
+        while let Some(id) = parent {
+            if id == ancestor {
+                return true;
+            }
+            parent = self.nodes[id.index()].parent;
+        }
+        false
+    }
+
+    /// The visible widget at `point` drawn last, usually the innermost one.
+    /// While a widget has an open [overlay](Widget::overlay), it is the
+    /// target of every point.
+    pub fn target<M: Copy>(&self, point: Point) -> Option<NodeId>
+    where
+        W: Widget<M>,
+    {
+        let visible = |node: &Node<W>| node.widget.is_visible();
+        self.nodes
+            .iter()
+            .rposition(|node| visible(node) && node.widget.overlay().is_some())
+            .or_else(|| {
+                self.nodes
+                    .iter()
+                    .rposition(|node| visible(node) && node.widget.bounding_box().contains(point))
+            })
+            .map(|index| NodeId {
+                index: index as u16,
+                generation: 0,
+            })
+    }
+
+    /// Hand `event` to `target` and the widgets around it. First the
+    /// ancestors of `target` see it in [`Widget::capture_event`], the root
+    /// first, then `target` and its ancestors in [`Widget::handle_event`],
+    /// innermost first. Any of them can consume the event, which ends the
+    /// dispatch. `sent` is called with every message produced and the id of
+    /// the widget that sent it. Returns whether the event was consumed.
+    ///
+    /// Afterwards paged containers on the way show their
+    /// [active child](Widget::active_child).
+    pub fn dispatch<M: Copy>(
+        &mut self,
+        target: NodeId,
+        event: InputEvent,
+        mut sent: impl FnMut(M, Option<WidgetId>),
+    ) -> bool
+    where
+        W: Widget<M>,
+    {
+        let mut path: Vec<NodeId, N> = Vec::new();
+        let mut node = Some(target).filter(|id| id.index() < self.nodes.len());
+        while let Some(id) = node {
+            let _ = path.push(id);
+            node = self.parent(id);
+        }
+        let mut visit = |arena: &mut Self, id: NodeId, capture: bool| {
+            let widget = &mut arena.nodes[id.index()].widget;
+            if !widget.is_visible() {
+                return false;
+            }
+            let response = if capture {
+                widget.capture_event(event)
+            } else {
+                widget.handle_event(event)
+            };
+            if let Some(message) = response.message() {
+                sent(message, widget.id());
+            }
+            response.is_consumed()
+        };
+        let consumed = path.iter().skip(1).rev().any(|&id| visit(self, id, true))
+            || path.iter().any(|&id| visit(self, id, false));
+        for &id in path.iter().rev() {
+            let widget = &self.nodes[id.index()].widget;
+            if let Some(active) = widget.active_child().filter(|_| widget.is_visible()) {
+                self.show_child(id, active);
+            }
+        }
+        consumed
+    }
+
+    /// Place the children of `parent` in equal cells of a row or column
+    /// filling it. Call again after showing or hiding a child to collapse
+    /// its space.
+    pub fn arrange<M: Copy>(
+        &mut self,
+        parent: NodeId,
+        orientation: Orientation,
+        spacing: u32,
+        hidden: Hidden,
+    ) where
+        W: Widget<M> + Place,
+    {
+        let Some(area) = self.get(parent).map(|widget| widget.bounding_box()) else {
+            return;
+        };
+        let children: Vec<NodeId, N> = self.children(parent).collect();
+        let shown = |arena: &Self, child: &NodeId| {
+            hidden == Hidden::Keep || arena.nodes[child.index()].widget.is_visible()
+        };
+        let count = children.iter().filter(|child| shown(self, child)).count();
+        let mut index = 0;
+        for child in children {
+            if shown(self, &child) {
+                let cell = split(area, orientation, count, index, spacing);
+                self.nodes[child.index()].widget.place(cell);
+                index += 1;
+            }
+        }
+    }
+}
+
+impl<W, const N: usize> Default for Arena<W, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Generate an enum over the widget types of an application.
+///
+/// Every widget must use the same message and colour types, given after
+/// `for`. The enum implements [`Widget`](crate::Widget),
+/// [`Element`](crate::screen::Element) for every draw target and theme,
+/// `Dimensions`, `Transform`, [`Place`](crate::layout::Place), and `From`
+/// and [`Variant`] for each widget type, so the widget types must differ.
+///
+/// ```
+/// # use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb888, prelude::*};
+/// # use embui::{themes::DefaultTheme, tree::Arena, widget_enum, widgets::{Button, Number}};
+/// #[derive(Clone, Copy)]
+/// enum Message {
+///     Reset,
+/// }
+///
+/// widget_enum! {
+///     pub enum AppWidget<'a> for Message, Rgb888 {
+///         Button(Button<'a, Message, Rgb888>),
+///         Number(Number<'a, Message>),
+///     }
+/// }
+///
+/// let mut ui: Arena<AppWidget, 4> = Arena::new();
+/// let panel = ui
+///     .insert(Number::new(Point::new(8, 8), Size::new(40, 12)))
+///     .ok()
+///     .unwrap();
+/// let reset = Button::new("Reset")
+///     .on_press(Message::Reset)
+///     .with_position(Point::new(8, 24))
+///     .with_size(Size::new(40, 16));
+/// ui.insert_child(panel, reset).ok().unwrap();
+///
+/// let mut display = MockDisplay::<Rgb888>::new();
+/// display.set_allow_overdraw(true);
+/// ui.draw(&mut display, &DefaultTheme::new()).unwrap();
+/// ```
+#[macro_export]
+macro_rules! widget_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident $(<$($lt:lifetime),+>)? for $msg:ty, $color:ty {
+            $($variant:ident($ty:ty)),* $(,)?
+        }
+    ) => {
+        $crate::widget_enum!(@emit
+            $(#[$meta])* $vis enum $name [$($($lt),+)?] [$($($lt),+)?] for $msg, $color {
+                $($variant($ty)),*
+            }
+        );
+    };
+
+    (@emit
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident $lifetimes:tt [$($lt:lifetime),*] for $msg:ty, $color:ty {
+            $($variant:ident($ty:ty)),*
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name<$($lt),*> {
+            $($variant($ty),)*
+        }
+
+        $($crate::widget_enum!(@variant $lifetimes $name $variant $ty, $msg);)*
+
+        impl <$($lt),*> $crate::Widget<$msg> for $name <$($lt),*> {
+            fn to_message(&self) -> Option<$msg> {
+                match self {
+                    $(Self::$variant(widget) => $crate::Widget::to_message(widget),)*
+                }
+            }
+            fn handle_event(&mut self, event: $crate::InputEvent) -> $crate::Response<$msg> {
+                match self {
+                    $(Self::$variant(widget) => $crate::Widget::handle_event(widget, event),)*
+                }
+            }
+            fn capture_event(&mut self, event: $crate::InputEvent) -> $crate::Response<$msg> {
+                match self {
+                    $(Self::$variant(widget) => $crate::Widget::capture_event(widget, event),)*
+                }
+            }
+            fn active_child(&self) -> Option<usize> {
+                match self {
+                    $(Self::$variant(widget) => $crate::Widget::active_child(widget),)*
+                }
+            }
+            fn overlay(&self) -> Option<$crate::__private::Rectangle> {
+                match self {
+                    $(Self::$variant(widget) => $crate::Widget::overlay(widget),)*
+                }
+            }
+            fn get_state(&self) -> $crate::WidgetState {
+                match self {
+                    $(Self::$variant(widget) => $crate::Widget::get_state(widget),)*
+                }
+            }
+            fn set_state(&mut self, state: $crate::WidgetState) -> bool {
+                match self {
+                    $(Self::$variant(widget) => $crate::Widget::set_state(widget, state),)*
+                }
+            }
+            fn is_dirty(&self) -> bool {
+                match self {
+                    $(Self::$variant(widget) => $crate::Widget::is_dirty(widget),)*
+                }
+            }
+            fn mark_clean(&mut self) {
+                match self {
+                    $(Self::$variant(widget) => $crate::Widget::mark_clean(widget),)*
+                }
+            }
+            fn get_state_manager(&self) -> &$crate::StateManager {
+                match self {
+                    $(Self::$variant(widget) => $crate::Widget::get_state_manager(widget),)*
+                }
+            }
+            fn get_state_manager_mut(&mut self) -> &mut $crate::StateManager {
+                match self {
+                    $(Self::$variant(widget) => $crate::Widget::get_state_manager_mut(widget),)*
+                }
+            }
+        }
+
+        impl<$($lt,)* __D, __T> $crate::ThemedWidget<__D, __T, $color> for $name <$($lt),*>
+        where
+            __D: $crate::__private::DrawTarget<Color = $color>,
+            __T: $crate::Theme<$color>,
+            $($ty: $crate::ThemedWidget<__D, __T, $color>,)*
+        {
+            fn draw_with_theme(&self, target: &mut __D, theme: &__T) -> Result<(), __D::Error> {
+                match self {
+                    $(Self::$variant(widget) => widget.draw_with_theme(target, theme),)*
+                }
+            }
+            fn draw_overlay(&self, target: &mut __D, theme: &__T) -> Result<(), __D::Error> {
+                match self {
+                    $(Self::$variant(widget) => widget.draw_overlay(target, theme),)*
+                }
+            }
+        }
+
+        impl<$($lt,)* __D, __T> $crate::screen::Element<$msg, __D, __T, $color>
+            for $name <$($lt),*>
+        where
+            __D: $crate::__private::DrawTarget<Color = $color>,
+            __T: $crate::Theme<$color>,
+            $($ty: $crate::ThemedWidget<__D, __T, $color>,)*
+        {
+        }
+
+        impl <$($lt),*> $crate::__private::Dimensions for $name <$($lt),*> {
+            fn bounding_box(&self) -> $crate::__private::Rectangle {
+                match self {
+                    $(Self::$variant(widget) => widget.bounding_box(),)*
+                }
+            }
+        }
+
+        impl <$($lt),*> $crate::__private::Transform for $name <$($lt),*> {
+            fn translate(&self, by: $crate::__private::Point) -> Self {
+                match self {
+                    $(Self::$variant(widget) => {
+                        Self::$variant($crate::__private::Transform::translate(widget, by))
+                    })*
+                }
+            }
+            fn translate_mut(&mut self, by: $crate::__private::Point) -> &mut Self {
+                match self {
+                    $(Self::$variant(widget) => {
+                        $crate::__private::Transform::translate_mut(widget, by);
+                    })*
+                }
+                self
+            }
+        }
+
+        impl <$($lt),*> $crate::layout::Place for $name <$($lt),*> {
+            fn place(&mut self, bounds: $crate::__private::Rectangle) {
+                match self {
+                    $(Self::$variant(widget) => $crate::layout::Place::place(widget, bounds),)*
+                }
+            }
+        }
+    };
+
+    (@variant [$($lt:lifetime),*] $name:ident $variant:ident $ty:ty, $msg:ty) => {
+        impl<$($lt),*> From<$ty> for $name<$($lt),*> {
+            fn from(widget: $ty) -> Self {
+                Self::$variant(widget)
+            }
+        }
+
+        impl<$($lt),*> $crate::tree::Variant<$ty> for $name<$($lt),*> {
+            type Message = $msg;
+
+            fn as_variant(&self) -> Option<&$ty> {
+                match self {
+                    Self::$variant(widget) => Some(widget),
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+            fn as_variant_mut(&mut self) -> Option<&mut $ty> {
+                match self {
+                    Self::$variant(widget) => Some(widget),
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::primitives::Rectangle;
+
+    use super::*;
+    use crate::{
+        Response, StateManager, ThemedWidget,
+        widgets::{Button, Number, ProgressBar, Tabs},
+    };
+
+    #[derive(Clone, Copy)]
+    enum Message {}
+
+    /// Swipes between pages on drags nothing inside it took
+    #[derive(Clone)]
+    struct Pager {
+        bounds: Rectangle,
+        state_manager: StateManager,
+        swipes: u32,
+        intercept: bool,
+    }
+
+    impl Widget<Message> for Pager {
+        fn to_message(&self) -> Option<Message> {
+            None
+        }
+        fn handle_event(&mut self, event: InputEvent) -> Response<Message> {
+            if matches!(event, InputEvent::TouchMove(_)) {
+                self.swipes += 1;
+                return Response::Consumed(None);
+            }
+            Response::NotChanged
+        }
+        fn capture_event(&mut self, event: InputEvent) -> Response<Message> {
+            if self.intercept {
+                self.handle_event(event)
+            } else {
+                Response::NotChanged
+            }
+        }
+        fn get_state_manager(&self) -> &StateManager {
+            &self.state_manager
+        }
+        fn get_state_manager_mut(&mut self) -> &mut StateManager {
+            &mut self.state_manager
+        }
+    }
+    impl<D: DrawTarget<Color = Rgb888>, T: Theme<Rgb888>> ThemedWidget<D, T, Rgb888> for Pager {
+        fn draw_with_theme(&self, _target: &mut D, _theme: &T) -> Result<(), D::Error> {
+            Ok(())
+        }
+    }
+    impl Dimensions for Pager {
+        fn bounding_box(&self) -> Rectangle {
+            self.bounds
+        }
+    }
+    impl Transform for Pager {
+        fn translate(&self, by: Point) -> Self {
+            let mut moved = self.clone();
+            moved.translate_mut(by);
+            moved
+        }
+        fn translate_mut(&mut self, by: Point) -> &mut Self {
+            self.bounds.top_left += by;
+            self
+        }
+    }
+    impl Place for Pager {
+        fn place(&mut self, bounds: Rectangle) {
+            self.bounds = bounds;
+        }
+    }
+
+    crate::widget_enum! {
+        enum Test<'a> for Message, Rgb888 {
+            Number(Number<'a, Message>),
+            Progress(ProgressBar<'a, Message, Rgb888>),
+            Button(Button<'a, Message, Rgb888>),
+            Pager(Pager),
+            Tabs(Tabs<'a, Message, Rgb888, 3>),
+        }
+    }
+
+    #[test]
+    fn children_keep_insertion_order() {
+        let mut arena: Arena<Test, 3> = Arena::new();
+        let number = || Number::new(Point::zero(), Size::new(10, 10));
+        let root = arena.insert(number()).ok().unwrap();
+        let first = arena.insert_child(root, number()).ok().unwrap();
+        let second = arena
+            .insert_child(root, ProgressBar::new(Point::zero(), Size::new(10, 4)))
+            .ok()
+            .unwrap();
+
+        assert!(arena.children(root).eq([first, second]));
+        assert_eq!(arena.parent(second), Some(root));
+        assert_eq!(arena.children(first).count(), 0);
+
+        // A parent from another arena is refused before anything is added
+        let mut other: Arena<Test, 3> = Arena::new();
+        assert!(other.insert_child(second, number()).is_err());
+        assert!(other.is_empty());
+        assert!(arena.insert(number()).is_err());
+    }
+
+    #[test]
+    fn hiding_cascades_and_collapses() {
+        let mut arena: Arena<Test, 4> = Arena::new();
+        let cell = || Number::new(Point::zero(), Size::zero());
+        let row = arena
+            .insert(Number::new(Point::zero(), Size::new(90, 10)))
+            .ok()
+            .unwrap();
+        let [a, b, c] = [(); 3].map(|_| arena.insert_child(row, cell()).ok().unwrap());
+
+        arena.set_enabled(row, false);
+        assert!(arena.iter().all(|widget| !widget.is_enabled()));
+
+        arena.set_visible(b, false);
+        arena.arrange(row, Orientation::Horizontal, 0, Hidden::Keep);
+        assert_eq!(arena.get(c).unwrap().bounding_box().top_left.x, 60);
+        arena.arrange(row, Orientation::Horizontal, 0, Hidden::Collapse);
+        assert_eq!(arena.get(a).unwrap().bounding_box().size.width, 45);
+        assert_eq!(arena.get(c).unwrap().bounding_box().top_left.x, 45);
+    }
+
+    #[test]
+    fn one_page_is_shown() {
+        let mut arena: Arena<Test, 4> = Arena::new();
+        let page = || Number::new(Point::zero(), Size::new(10, 10));
+        let pages = arena.insert(page()).ok().unwrap();
+        let [a, b, c] = [(); 3].map(|_| arena.insert_child(pages, page()).ok().unwrap());
+
+        arena.show_child(pages, 1);
+        let shown = [a, b, c].map(|page| arena.get(page).unwrap().is_visible());
+        assert_eq!(shown, [false, true, false]);
+        assert_eq!(arena.target(Point::new(5, 5)), Some(b));
+    }
+
+    #[test]
+    fn tabs_show_only_the_active_page() {
+        let mut arena: Arena<Test, 4> = Arena::new();
+        let tabs = Tabs::new(
+            Rectangle::new(Point::zero(), Size::new(60, 40)),
+            ["A", "B", "C"],
+        );
+        let (content, header) = (tabs.content(), tabs.header(2).center());
+        let tabs = arena.insert(tabs).ok().unwrap();
+        let page = || Number::new(content.top_left, content.size);
+        let [a, b, c] = [(); 3].map(|_| arena.insert_child(tabs, page()).ok().unwrap());
+        let shown =
+            |arena: &Arena<Test, 4>| [a, b, c].map(|page| arena.get(page).unwrap().is_visible());
+        assert_eq!(shown(&arena), [true, false, false]);
+
+        assert_eq!(arena.target(header), Some(tabs));
+        assert!(arena.dispatch(tabs, InputEvent::Touch(header), |_, _| {}));
+        assert_eq!(shown(&arena), [false, false, true]);
+        assert_eq!(arena.target(content.center()), Some(c));
+
+        // Showing the tabs again leaves the other pages hidden
+        arena.set_visible(tabs, false);
+        arena.set_visible(tabs, true);
+        assert_eq!(shown(&arena), [false, false, true]);
+    }
+
+    #[test]
+    fn widgets_are_found_by_id() {
+        const LEVEL: WidgetId = WidgetId(7);
+        let mut arena: Arena<Test, 2> = Arena::new();
+        let _ = arena.insert(Number::new(Point::zero(), Size::new(10, 10)));
+        let level = ProgressBar::new(Point::zero(), Size::new(10, 4)).with_id(LEVEL);
+        let node = arena.insert(level).ok().unwrap();
+
+        assert_eq!(arena.find(LEVEL), Some(node));
+        assert!(arena.widget::<Number<Message>>(LEVEL).is_none());
+        let level = arena.widget_mut::<ProgressBar<Message, Rgb888>>(LEVEL);
+        assert!(level.is_some());
+    }
+
+    #[test]
+    fn events_bubble_until_consumed() {
+        let mut arena: Arena<Test, 3> = Arena::new();
+        let pager = Pager {
+            bounds: Rectangle::new(Point::zero(), Size::new(40, 40)),
+            state_manager: StateManager::read_only(),
+            swipes: 0,
+            intercept: false,
+        };
+        let pager = arena.insert(pager).ok().unwrap();
+        let button = Button::new("-").with_size(Size::new(20, 10));
+        let button = arena.insert_child(pager, button).ok().unwrap();
+        let bar = ProgressBar::new(Point::new(0, 20), Size::new(20, 10));
+        arena.insert_child(pager, bar).ok().unwrap();
+        let swipes = |arena: &Arena<Test, 3>| match arena.get(pager) {
+            Some(Test::Pager(pager)) => pager.swipes,
+            _ => unreachable!(),
+        };
+
+        // The pressed button takes the drag
+        let on_button = Point::new(5, 5);
+        assert_eq!(arena.target(on_button), Some(button));
+        assert!(arena.dispatch(button, InputEvent::Touch(on_button), |_, _| {}));
+        assert!(arena.dispatch(button, InputEvent::TouchMove(on_button), |_, _| {}));
+        assert_eq!(swipes(&arena), 0);
+
+        // The progress bar doesn't, so it bubbles up to the pager
+        let on_bar = Point::new(5, 25);
+        let bar = arena.target(on_bar).unwrap();
+        assert!(arena.dispatch(bar, InputEvent::TouchMove(on_bar), |_, _| {}));
+        assert_eq!(swipes(&arena), 1);
+
+        // An intercepting pager sees the drag before the button
+        if let Some(Test::Pager(pager)) = arena.get_mut(pager) {
+            pager.intercept = true;
+        }
+        assert!(arena.dispatch(button, InputEvent::TouchMove(on_button), |_, _| {}));
+        assert_eq!(swipes(&arena), 2);
+    }
+}
@@ -1,15 +1,13 @@
-use core::marker::PhantomData;
-
 use crate::{
     StateManager, ThemedWidget, Widget, WidgetState,
+    i18n::Text,
+    layout::Place,
     screen::Element,
-    themes::{DefaultTheme, Theme},
+    themes::{DefaultTheme, Style, Theme},
+    widgets::{frame::Frame, icon::Icon},
 };
 use embedded_graphics::{
-    mono_font::MonoTextStyle,
-    pixelcolor::Rgb888,
-    prelude::*,
-    primitives::{PrimitiveStyleBuilder, Rectangle, StyledDrawable},
+    mono_font::MonoTextStyle, pixelcolor::Rgb888, prelude::*, primitives::Rectangle,
 };
 use embedded_text::{
     TextBox,
@@ -17,17 +15,31 @@ use embedded_text::{
     style::{HeightMode, TextBoxStyleBuilder, VerticalOverdraw},
 };
 
+/// Where a [`Button`] shows its icon relative to the label.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum IconPlacement {
+    /// Icon before the label, centred together. In right-to-left locales
+    /// the icon goes right of the label.
+    #[default]
+    Left,
+    /// Icon above the label, centred together
+    Top,
+    /// Icon only, the label is not drawn
+    Only,
+}
+
 #[derive(Clone, Debug)]
 pub struct Button<'a, M, C>
 where
     M: Copy + Clone,
     C: PixelColor,
 {
-    ph: PhantomData<C>,
-    label: &'a str,
+    label: Text<'a>,
     pos: Option<Point>,
     size: Option<Size>,
     on_press: Option<M>,
+    icon: Option<(Icon, IconPlacement)>,
+    style: Style<C>,
     state_manager: StateManager,
 }
 
@@ -36,13 +48,15 @@ where
     M: Copy + Clone,
     C: PixelColor,
 {
-    pub fn new(label: &'a str) -> Self {
+    /// A button showing `label`, a string or a [`StringId`](crate::i18n::StringId)
+    pub fn new(label: impl Into<Text<'a>>) -> Self {
         Self {
-            ph: PhantomData,
-            label,
+            label: label.into(),
             pos: None,
             size: None,
             on_press: None,
+            icon: None,
+            style: Style::new(),
             state_manager: StateManager::default(),
         }
     }
@@ -59,6 +73,16 @@ where
         self.pos = Some(pos);
         self
     }
+    /// Show an icon, tinted with the label colour
+    pub fn with_icon(mut self, icon: Icon, placement: IconPlacement) -> Self {
+        self.icon = Some((icon, placement));
+        self
+    }
+    /// Override theme colours, font, padding or alignment for this button
+    pub fn with_style(mut self, style: Style<C>) -> Self {
+        self.style = style;
+        self
+    }
 }
 
 impl<M, C> Widget<M> for Button<'_, M, C>
@@ -87,59 +111,87 @@ where
     M: Copy,
 {
     fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), <D as DrawTarget>::Error> {
-        let (background_color, text_color, border_color) = match Widget::get_state(self) {
-            WidgetState::Normal => (
-                theme.button_normal_bg(),
-                theme.button_normal_text(),
-                theme.button_normal_border(),
-            ),
-            WidgetState::Hovered => (
-                theme.button_hovered_bg(),
-                theme.button_hovered_text(),
-                theme.button_hovered_border(),
-            ),
-            WidgetState::Pressed => (
-                theme.button_pressed_bg(),
-                theme.button_pressed_text(),
-                theme.button_pressed_border(),
-            ),
-            WidgetState::Focused => (
-                theme.button_hovered_bg(),
-                theme.button_hovered_text(),
-                theme.primary_color(), // Different border for focus
-            ),
-            WidgetState::Disabled => (
-                theme.button_disabled_bg(),
-                theme.button_disabled_text(),
-                theme.button_disabled_border(),
-            ),
+        let flags = self.state_flags();
+        let state = flags.state();
+        let style = self.style.resolve(theme, flags);
+        let bounds = self.bounding_box();
+        Frame::themed(
+            bounds.offset(-(theme.spacing_xs() as i32)),
+            theme,
+            flags,
+            style.background,
+            style.border,
+        )
+        .draw(target)?;
+
+        // Content sinks along with a pressed frame
+        let bounds = if state == WidgetState::Pressed {
+            bounds.translate(theme.button_pressed_offset())
+        } else {
+            bounds
         };
+        let content = bounds.offset(-(style.padding as i32));
+        let font = style.font;
+        let text = self.label.resolve(theme);
+        let mut label_area = content;
+        if let Some((icon, placement)) = self.icon {
+            let icon_size = icon.size();
+            let gap = theme.spacing_xs();
+            let center = content.center();
+            let icon_pos = match placement {
+                IconPlacement::Left => {
+                    let label_width = text.chars().count() as u32
+                        * (font.character_size.width + font.character_spacing);
+                    let width = icon_size.width + gap + label_width;
+                    let left = match style.alignment {
+                        HorizontalAlignment::Left => content.top_left.x,
+                        HorizontalAlignment::Right => {
+                            content.top_left.x + content.size.width as i32 - width as i32
+                        }
+                        _ => center.x - (width / 2) as i32,
+                    };
+                    let label = Rectangle::new(
+                        Point::new(left + (icon_size.width + gap) as i32, content.top_left.y),
+                        Size::new(label_width, content.size.height),
+                    );
+                    let icon = Rectangle::new(
+                        Point::new(left, center.y - (icon_size.height / 2) as i32),
+                        icon_size,
+                    );
+                    let group = Rectangle::new(
+                        Point::new(left, content.top_left.y),
+                        Size::new(width, content.size.height),
+                    );
+                    let direction = theme.direction();
+                    label_area = direction.mirror(label, group);
+                    direction.mirror(icon, group).top_left
+                }
+                IconPlacement::Top => {
+                    let label_height = font.character_size.height;
+                    let height = icon_size.height + gap + label_height;
+                    let top = center.y - (height / 2) as i32;
+                    label_area = Rectangle::new(
+                        Point::new(content.top_left.x, top + (icon_size.height + gap) as i32),
+                        Size::new(content.size.width, label_height),
+                    );
+                    Point::new(center.x - (icon_size.width / 2) as i32, top)
+                }
+                IconPlacement::Only => center - icon_size / 2,
+            };
+            icon.draw_tinted(icon_pos, style.text, target)?;
+            if placement == IconPlacement::Only {
+                return Ok(());
+            }
+        }
+
         let textbox_style = TextBoxStyleBuilder::new()
             .height_mode(HeightMode::Exact(VerticalOverdraw::Hidden))
-            .alignment(HorizontalAlignment::Center)
+            .alignment(style.alignment)
             .vertical_alignment(VerticalAlignment::Middle)
             .paragraph_spacing(6)
             .build();
-        let character_style = MonoTextStyle::new(theme.normal_font(), text_color);
-        let label = TextBox::with_textbox_style(
-            self.label,
-            self.bounding_box(),
-            character_style,
-            textbox_style,
-        );
-
-        let outline_style = PrimitiveStyleBuilder::new()
-            .fill_color(background_color)
-            .stroke_color(border_color)
-            .stroke_width(theme.spacing_xs())
-            .build();
-
-        let outline = Rectangle::new(
-            self.pos.unwrap_or(Point::zero()) + Point::new(2, 2),
-            self.size.unwrap_or(Size::new(16, 16)) - Size::new(4, 4),
-        );
-
-        outline.draw_styled(&outline_style, target)?;
+        let character_style = MonoTextStyle::new(font, style.text);
+        let label = TextBox::with_textbox_style(text, label_area, character_style, textbox_style);
         label.draw(target)?;
         Ok(())
     }
@@ -193,6 +245,17 @@ where
         self
     }
 }
+impl<M, C> Place for Button<'_, M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn place(&mut self, bounds: Rectangle) {
+        self.pos = Some(bounds.top_left);
+        self.size = Some(bounds.size);
+        self.state_manager.mark_dirty();
+    }
+}
 
 impl<M, C> Dimensions for Button<'_, M, C>
 where
@@ -205,3 +268,61 @@ where
         Rectangle::new(pos, size)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::mock_display::MockDisplay;
+
+    use super::*;
+
+    #[derive(Clone, Copy, Debug)]
+    enum Message {}
+
+    const SQUARE: Icon = Icon::new(4, 4, &[0xF0; 4]);
+
+    #[test]
+    fn icon_is_centred_with_the_label() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let button: Button<Message, Rgb888> = Button::new("ab")
+            .with_size(Size::new(40, 20))
+            .with_icon(SQUARE, IconPlacement::Left);
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        display.set_allow_out_of_bounds_drawing(true);
+        button.draw_with_theme(&mut display, &theme).unwrap();
+
+        // Icon, 2 pixel gap and two 6 pixel characters make 18 pixels,
+        // centred on x = 19 in the padded content
+        let text = theme.button_normal_text();
+        assert_eq!(display.get_pixel(Point::new(10, 7)), Some(text));
+        assert_eq!(display.get_pixel(Point::new(13, 10)), Some(text));
+        assert_eq!(
+            display.get_pixel(Point::new(9, 8)),
+            Some(theme.button_normal_bg())
+        );
+        assert_eq!(
+            display.get_pixel(Point::new(14, 8)),
+            Some(theme.button_normal_bg())
+        );
+    }
+
+    #[test]
+    fn icon_only_skips_the_label() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let button: Button<Message, Rgb888> = Button::new("ab")
+            .with_size(Size::new(20, 20))
+            .with_icon(SQUARE, IconPlacement::Only);
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        button.draw_with_theme(&mut display, &theme).unwrap();
+
+        let text = theme.button_normal_text();
+        let drawn = display
+            .bounding_box()
+            .points()
+            .filter(|&point| display.get_pixel(point) == Some(text))
+            .count();
+        assert_eq!(drawn, 16);
+        assert_eq!(display.get_pixel(Point::new(8, 8)), Some(text));
+    }
+}
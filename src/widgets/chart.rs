@@ -0,0 +1,681 @@
+use core::{fmt::Write, marker::PhantomData};
+
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{Line, PrimitiveStyle, Rectangle, StyledDrawable},
+    text::{Baseline, Text},
+};
+use heapless::HistoryBuffer;
+
+use crate::{StateManager, Theme, ThemedWidget, Widget, widgets::frame::Frame};
+
+/// Vertical value range of a chart.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum YRange {
+    /// Fit the range to the buffered samples
+    #[default]
+    Auto,
+    /// Always map `min..=max` onto the plot height, clamping outliers. The
+    /// ends are swapped if `min` is above `max`.
+    Fixed { min: i32, max: i32 },
+}
+
+/// How appended samples move across a [`LineChart`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ScrollMode {
+    /// The newest sample is always on the right edge, so every append
+    /// shifts the whole trace and needs a full redraw.
+    #[default]
+    Shift,
+    /// A write cursor sweeps left to right and wraps around, like a patient
+    /// monitor. Every append only touches one column, see
+    /// [`LineChart::draw_latest`].
+    Sweep,
+}
+
+/// Number of grid divisions drawn behind the plot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grid {
+    pub rows: u32,
+    pub columns: u32,
+}
+
+/// Value limits that map a reading onto the theme's status colours.
+///
+/// With `warning < error` high values are bad (temperature, load); with
+/// `warning > error` the scale is inverted and low values are bad (battery).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thresholds {
+    pub warning: i32,
+    pub error: i32,
+}
+
+impl Thresholds {
+    pub const fn new(warning: i32, error: i32) -> Self {
+        Self { warning, error }
+    }
+
+    /// Status colour for `value`
+    pub fn color<C, T>(&self, theme: &T, value: i32) -> C
+    where
+        C: PixelColor,
+        T: Theme<C>,
+    {
+        let (warning, error) = if self.warning <= self.error {
+            (value >= self.warning, value >= self.error)
+        } else {
+            (value <= self.warning, value <= self.error)
+        };
+        if error {
+            theme.error_color()
+        } else if warning {
+            theme.warning_color()
+        } else {
+            theme.success_color()
+        }
+    }
+}
+
+/// Decorations shared by all chart types: background, frame, grid and
+/// min/max labels on the Y axis.
+#[derive(Debug, Clone, Copy)]
+struct Axes {
+    grid: Option<Grid>,
+    labels: bool,
+    frame: bool,
+}
+
+struct Layout {
+    inner: Rectangle,
+    plot: Rectangle,
+}
+
+impl Axes {
+    fn layout<C, T>(&self, bounds: Rectangle, theme: &T, min: i32, max: i32) -> Layout
+    where
+        C: PixelColor,
+        T: Theme<C>,
+    {
+        let inner = if self.frame {
+            bounds.offset(-((theme.panel_border_width() + theme.spacing_xs()) as i32))
+        } else {
+            bounds
+        };
+        if !self.labels {
+            return Layout { inner, plot: inner };
+        }
+        let chars = format_value(min).len().max(format_value(max).len()) as u32;
+        let label_width = (chars * theme.small_font().character_size.width + theme.spacing_xs())
+            .min(inner.size.width);
+        let plot = Rectangle::new(
+            inner.top_left + Point::new(label_width as i32, 0),
+            Size::new(inner.size.width - label_width, inner.size.height),
+        );
+        Layout { inner, plot }
+    }
+
+    fn draw<D, T, C>(
+        &self,
+        bounds: Rectangle,
+        layout: &Layout,
+        min: i32,
+        max: i32,
+        target: &mut D,
+        theme: &T,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        T: Theme<C>,
+        C: PixelColor + From<Rgb888>,
+    {
+        let mut background = Frame::new(bounds).with_fill(theme.chart_background_color());
+        if self.frame {
+            background = background
+                .with_radius(theme.panel_corner_radius())
+                .with_border(theme.panel_border_color(), theme.panel_border_width());
+        }
+        background.draw(target)?;
+        self.draw_grid(layout.plot, target, theme)?;
+
+        if self.labels {
+            let style = MonoTextStyle::new(theme.small_font(), theme.chart_axis_text_color());
+            let bottom = layout.plot.top_left.y + layout.plot.size.height as i32;
+            Text::with_baseline(
+                format_value(max).as_str(),
+                Point::new(layout.inner.top_left.x, layout.plot.top_left.y),
+                style,
+                Baseline::Top,
+            )
+            .draw(target)?;
+            Text::with_baseline(
+                format_value(min).as_str(),
+                Point::new(layout.inner.top_left.x, bottom),
+                style,
+                Baseline::Bottom,
+            )
+            .draw(target)?;
+        }
+        Ok(())
+    }
+
+    fn draw_grid<D, T, C>(&self, plot: Rectangle, target: &mut D, theme: &T) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        T: Theme<C>,
+        C: PixelColor,
+    {
+        let (Some(grid), Some(bottom_right)) = (self.grid, plot.bottom_right()) else {
+            return Ok(());
+        };
+        let style = PrimitiveStyle::with_stroke(theme.chart_grid_color(), 1);
+        let top_left = plot.top_left;
+        for row in 0..=grid.rows.max(1) {
+            let y = top_left.y + (row * (plot.size.height - 1) / grid.rows.max(1)) as i32;
+            Line::new(Point::new(top_left.x, y), Point::new(bottom_right.x, y))
+                .draw_styled(&style, target)?;
+        }
+        for column in 0..=grid.columns.max(1) {
+            let x = top_left.x + (column * (plot.size.width - 1) / grid.columns.max(1)) as i32;
+            Line::new(Point::new(x, top_left.y), Point::new(x, bottom_right.y))
+                .draw_styled(&style, target)?;
+        }
+        Ok(())
+    }
+}
+
+/// Maps sample slots and values onto plot pixels.
+struct Scale {
+    area: Rectangle,
+    min: i32,
+    max: i32,
+}
+
+impl Scale {
+    fn x(&self, slot: usize, slots: usize) -> i32 {
+        let width = self.area.size.width.saturating_sub(1) as i64;
+        let steps = slots.max(2) as i64 - 1;
+        self.area.top_left.x + (slot as i64 * width / steps) as i32
+    }
+
+    fn y(&self, value: i32) -> i32 {
+        let height = self.area.size.height.saturating_sub(1) as i64;
+        let span = (self.max as i64 - self.min as i64).max(1);
+        let offset = (value as i64).clamp(self.min as i64, self.max as i64) - self.min as i64;
+        self.area.top_left.y + (height - offset * height / span) as i32
+    }
+}
+
+fn format_value(value: i32) -> heapless::String<11> {
+    let mut text = heapless::String::new();
+    write!(&mut text, "{}", value).unwrap();
+    text
+}
+
+fn auto_range<'a>(values: impl Iterator<Item = &'a i32>) -> (i32, i32) {
+    let (min, max) = values.fold((i32::MAX, i32::MIN), |(min, max), &v| {
+        (min.min(v), max.max(v))
+    });
+    if min > max {
+        (0, 1)
+    } else if min == max {
+        (min.saturating_sub(1), max.saturating_add(1))
+    } else {
+        (min, max)
+    }
+}
+
+/// A line chart of the last `N` samples of `S` series.
+///
+/// Samples are kept in fixed-capacity ring buffers, so once a series is full
+/// the oldest sample is dropped on every [`push`](Self::push).
+#[derive(Clone, Widget)]
+pub struct LineChart<M, C, const N: usize, const S: usize = 1>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    ph: PhantomData<M>,
+    series: [HistoryBuffer<i32, N>; S],
+    colors: [Option<C>; S],
+    range: YRange,
+    axes: Axes,
+    mode: ScrollMode,
+    cursor: usize,
+    #[position]
+    pos: Point,
+    #[size]
+    size: Size,
+    #[state]
+    state_manager: StateManager,
+}
+
+/// A single-series [`LineChart`] without frame, grid or labels, see
+/// [`LineChart::sparkline`].
+pub type Sparkline<M, C, const N: usize> = LineChart<M, C, N, 1>;
+
+impl<M, C, const N: usize, const S: usize> LineChart<M, C, N, S>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// A framed chart with Y axis labels and an auto-scaling range
+    pub fn new(pos: Point, size: Size) -> Self {
+        Self {
+            ph: PhantomData,
+            series: core::array::from_fn(|_| HistoryBuffer::new()),
+            colors: [None; S],
+            range: YRange::Auto,
+            axes: Axes {
+                grid: None,
+                labels: true,
+                frame: true,
+            },
+            mode: ScrollMode::Shift,
+            cursor: 0,
+            pos,
+            size,
+            state_manager: StateManager::read_only(),
+        }
+    }
+    /// A bare trace that fills its whole bounding box
+    pub fn sparkline(pos: Point, size: Size) -> Self {
+        let mut chart = Self::new(pos, size);
+        chart.axes.labels = false;
+        chart.axes.frame = false;
+        chart
+    }
+
+    pub fn with_range(mut self, range: YRange) -> Self {
+        self.range = range;
+        self
+    }
+    pub fn with_grid(mut self, rows: u32, columns: u32) -> Self {
+        self.axes.grid = Some(Grid { rows, columns });
+        self
+    }
+    pub fn with_axis_labels(mut self, labels: bool) -> Self {
+        self.axes.labels = labels;
+        self
+    }
+    pub fn with_frame(mut self, frame: bool) -> Self {
+        self.axes.frame = frame;
+        self
+    }
+    pub fn with_mode(mut self, mode: ScrollMode) -> Self {
+        self.mode = mode;
+        self
+    }
+    /// Override the theme's [`chart_series_color`](Theme::chart_series_color).
+    /// Series beyond `S` are ignored.
+    pub fn with_series_color(mut self, series: usize, color: C) -> Self {
+        if let Some(slot) = self.colors.get_mut(series) {
+            *slot = Some(color);
+        }
+        self
+    }
+
+    /// Append one sample to every series
+    pub fn push(&mut self, values: [i32; S]) {
+        for (series, value) in self.series.iter_mut().zip(values) {
+            series.write(value);
+        }
+        self.cursor = (self.cursor + 1) % N;
+        self.state_manager.mark_dirty();
+    }
+    pub fn clear(&mut self) {
+        self.series.iter_mut().for_each(HistoryBuffer::clear);
+        self.cursor = 0;
+        self.state_manager.mark_dirty();
+    }
+    pub fn series(&self, index: usize) -> &HistoryBuffer<i32, N> {
+        &self.series[index]
+    }
+    /// Number of samples currently buffered per series
+    pub fn len(&self) -> usize {
+        self.series.first().map_or(0, HistoryBuffer::len)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The range the next draw will use
+    pub fn y_range(&self) -> (i32, i32) {
+        match self.range {
+            YRange::Fixed { min, max } => (min.min(max), min.max(max)),
+            YRange::Auto => auto_range(self.series.iter().flat_map(|s| s.iter())),
+        }
+    }
+
+    /// Slot (column index) of the `k`-th oldest buffered sample
+    fn slot(&self, k: usize) -> usize {
+        match self.mode {
+            ScrollMode::Shift => k,
+            ScrollMode::Sweep => (self.cursor + N - self.len() + k) % N,
+        }
+    }
+
+    /// Draw only the column of the most recent sample.
+    ///
+    /// In [`ScrollMode::Sweep`] this clears the strip between the previous
+    /// and the newest sample and draws the new segment into it, leaving the
+    /// rest of the chart untouched. Pair it with a [`YRange::Fixed`] range,
+    /// since an auto range that changes would need a full redraw. In
+    /// [`ScrollMode::Shift`] this falls back to a full redraw.
+    pub fn draw_latest<D, T>(&self, target: &mut D, theme: &T) -> Result<(), D::Error>
+    where
+        C: Default + From<Rgb888>,
+        D: DrawTarget<Color = C>,
+        T: Theme<C>,
+    {
+        if self.mode != ScrollMode::Sweep || self.is_empty() {
+            return self.draw_with_theme(target, theme);
+        }
+        let (min, max) = self.y_range();
+        let layout = self.axes.layout(self.bounding_box(), theme, min, max);
+        let scale = Scale {
+            area: layout.plot,
+            min,
+            max,
+        };
+        let Some(bottom_right) = layout.plot.bottom_right() else {
+            return Ok(());
+        };
+
+        let slot = self.slot(self.len() - 1);
+        let right = scale.x(slot, N);
+        let left = if slot == 0 {
+            right
+        } else {
+            (scale.x(slot - 1, N) + 1).min(right)
+        };
+        let strip = Rectangle::with_corners(
+            Point::new(left, layout.plot.top_left.y),
+            Point::new(right, bottom_right.y),
+        );
+
+        let mut clipped = target.clipped(&strip);
+        strip.draw_styled(
+            &PrimitiveStyle::with_fill(theme.chart_background_color()),
+            &mut clipped,
+        )?;
+        self.axes.draw_grid(layout.plot, &mut clipped, theme)?;
+        for (index, series) in self.series.iter().enumerate() {
+            let style = PrimitiveStyle::with_stroke(self.series_color(index, theme), 1);
+            let mut newest = series.oldest_ordered().skip(series.len().saturating_sub(2));
+            let (first, second) = (newest.next(), newest.next());
+            let (from, to) = match (first, second) {
+                (Some(&prev), Some(&last)) if slot != 0 => (
+                    Point::new(scale.x(slot - 1, N), scale.y(prev)),
+                    Point::new(right, scale.y(last)),
+                ),
+                (Some(&last), None) | (Some(_), Some(&last)) => {
+                    let p = Point::new(right, scale.y(last));
+                    (p, p)
+                }
+                _ => continue,
+            };
+            Line::new(from, to).draw_styled(&style, &mut clipped)?;
+        }
+        Ok(())
+    }
+
+    fn series_color<T: Theme<C>>(&self, index: usize, theme: &T) -> C {
+        self.colors[index].unwrap_or_else(|| theme.chart_series_color(index))
+    }
+}
+
+impl<D, T, C, M, const N: usize, const S: usize> ThemedWidget<D, T, C> for LineChart<M, C, N, S>
+where
+    C: PixelColor + Default + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    M: Copy,
+{
+    fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), <D as DrawTarget>::Error> {
+        let (min, max) = self.y_range();
+        let bounds = self.bounding_box();
+        let layout = self.axes.layout(bounds, theme, min, max);
+        self.axes.draw(bounds, &layout, min, max, target, theme)?;
+
+        let scale = Scale {
+            area: layout.plot,
+            min,
+            max,
+        };
+        for (index, series) in self.series.iter().enumerate() {
+            let color = self.series_color(index, theme);
+            let style = PrimitiveStyle::with_stroke(color, 1);
+            let mut prev: Option<Point> = None;
+            for (k, &value) in series.oldest_ordered().enumerate() {
+                let slot = self.slot(k);
+                let point = Point::new(scale.x(slot, N), scale.y(value));
+                match prev {
+                    // A sweep wrapping back to slot 0 starts a new trace
+                    Some(from) if slot != 0 => {
+                        Line::new(from, point).draw_styled(&style, target)?
+                    }
+                    _ => Pixel(point, color).draw(target)?,
+                }
+                prev = Some(point);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A bar per sample for the last `N` samples, optionally coloured by
+/// [`Thresholds`].
+///
+/// With an auto range, zero is always included so bars grow from a visible
+/// baseline.
+#[derive(Clone, Widget)]
+pub struct BarChart<M, C, const N: usize>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    ph: PhantomData<M>,
+    values: HistoryBuffer<i32, N>,
+    color: Option<C>,
+    thresholds: Option<Thresholds>,
+    range: YRange,
+    axes: Axes,
+    #[position]
+    pos: Point,
+    #[size]
+    size: Size,
+    #[state]
+    state_manager: StateManager,
+}
+
+impl<M, C, const N: usize> BarChart<M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    pub fn new(pos: Point, size: Size) -> Self {
+        Self {
+            ph: PhantomData,
+            values: HistoryBuffer::new(),
+            color: None,
+            thresholds: None,
+            range: YRange::Auto,
+            axes: Axes {
+                grid: None,
+                labels: true,
+                frame: true,
+            },
+            pos,
+            size,
+            state_manager: StateManager::read_only(),
+        }
+    }
+
+    pub fn with_range(mut self, range: YRange) -> Self {
+        self.range = range;
+        self
+    }
+    pub fn with_grid(mut self, rows: u32, columns: u32) -> Self {
+        self.axes.grid = Some(Grid { rows, columns });
+        self
+    }
+    pub fn with_axis_labels(mut self, labels: bool) -> Self {
+        self.axes.labels = labels;
+        self
+    }
+    pub fn with_frame(mut self, frame: bool) -> Self {
+        self.axes.frame = frame;
+        self
+    }
+    pub fn with_color(mut self, color: C) -> Self {
+        self.color = Some(color);
+        self
+    }
+    /// Colour each bar by its value instead of using a single colour
+    pub fn with_thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.thresholds = Some(thresholds);
+        self
+    }
+
+    pub fn push(&mut self, value: i32) {
+        self.values.write(value);
+        self.state_manager.mark_dirty();
+    }
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.state_manager.mark_dirty();
+    }
+    pub fn values(&self) -> &HistoryBuffer<i32, N> {
+        &self.values
+    }
+
+    pub fn y_range(&self) -> (i32, i32) {
+        match self.range {
+            YRange::Fixed { min, max } => (min.min(max), min.max(max)),
+            YRange::Auto => auto_range(self.values.iter().chain(&[0])),
+        }
+    }
+}
+
+impl<D, T, C, M, const N: usize> ThemedWidget<D, T, C> for BarChart<M, C, N>
+where
+    C: PixelColor + Default + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    M: Copy,
+{
+    fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), <D as DrawTarget>::Error> {
+        let (min, max) = self.y_range();
+        let bounds = self.bounding_box();
+        let layout = self.axes.layout(bounds, theme, min, max);
+        self.axes.draw(bounds, &layout, min, max, target, theme)?;
+
+        let scale = Scale {
+            area: layout.plot,
+            min,
+            max,
+        };
+        let baseline = scale.y(0);
+        let width = layout.plot.size.width as usize;
+        for (i, &value) in self.values.oldest_ordered().enumerate() {
+            let left = layout.plot.top_left.x + (i * width / N) as i32;
+            let right = layout.plot.top_left.x + ((i + 1) * width / N) as i32 - 1;
+            // Leave a one pixel gap between bars that are wide enough
+            let right = if right - left >= 2 { right - 1 } else { right };
+            let color = match self.thresholds {
+                Some(thresholds) => thresholds.color(theme, value),
+                None => self.color.unwrap_or_else(|| theme.chart_series_color(0)),
+            };
+            Rectangle::with_corners(
+                Point::new(left, baseline),
+                Point::new(right.max(left), scale.y(value)),
+            )
+            .draw_styled(&PrimitiveStyle::with_fill(color), target)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::mock_display::MockDisplay;
+
+    use super::*;
+    use crate::themes::DefaultTheme;
+
+    #[derive(Clone, Copy)]
+    enum Message {}
+
+    #[test]
+    fn thresholds_pick_status_colors() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let load = Thresholds::new(70, 90);
+        assert_eq!(load.color(&theme, 10), theme.success_color());
+        assert_eq!(load.color(&theme, 70), theme.warning_color());
+        assert_eq!(load.color(&theme, 95), theme.error_color());
+
+        let battery = Thresholds::new(20, 5);
+        assert_eq!(battery.color(&theme, 80), theme.success_color());
+        assert_eq!(battery.color(&theme, 15), theme.warning_color());
+        assert_eq!(battery.color(&theme, 5), theme.error_color());
+    }
+
+    #[test]
+    fn auto_range_fits_all_series() {
+        let mut chart: LineChart<Message, Rgb888, 4, 2> =
+            LineChart::new(Point::zero(), Size::new(32, 32));
+        assert_eq!(chart.y_range(), (0, 1));
+        chart.push([3, -2]);
+        chart.push([7, 1]);
+        assert_eq!(chart.y_range(), (-2, 7));
+        for _ in 0..4 {
+            chart.push([5, 5]);
+        }
+        assert_eq!(chart.len(), 4);
+        assert_eq!(chart.y_range(), (4, 6));
+    }
+
+    #[test]
+    fn reversed_fixed_range_is_swapped() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let reversed = YRange::Fixed { min: 60, max: -20 };
+        let mut chart: LineChart<Message, Rgb888, 4, 1> =
+            LineChart::new(Point::zero(), Size::new(32, 32)).with_range(reversed);
+        chart.push([10]);
+        chart.push([70]);
+        assert_eq!(chart.y_range(), (-20, 60));
+        let mut bars: BarChart<Message, Rgb888, 2> =
+            BarChart::new(Point::zero(), Size::new(32, 32)).with_range(reversed);
+        bars.push(10);
+        assert_eq!(bars.y_range(), (-20, 60));
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        display.set_allow_out_of_bounds_drawing(true);
+        chart.draw_with_theme(&mut display, &theme).unwrap();
+        bars.draw_with_theme(&mut display, &theme).unwrap();
+    }
+
+    #[test]
+    fn sweep_draws_only_latest_column() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let mut chart: Sparkline<Message, Rgb888, 8> =
+            LineChart::sparkline(Point::zero(), Size::new(15, 16))
+                .with_mode(ScrollMode::Sweep)
+                .with_range(YRange::Fixed { min: 0, max: 15 });
+        for value in [1, 4, 9] {
+            chart.push([value]);
+        }
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        chart.draw_latest(&mut display, &theme).unwrap();
+        // Slot 2 of 8 across 15 pixels spans the columns after slot 1
+        assert_eq!(
+            display.affected_area(),
+            Rectangle::new(Point::new(3, 0), Size::new(2, 16))
+        );
+    }
+}
@@ -0,0 +1,452 @@
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle, Triangle},
+};
+use embedded_text::{
+    TextBox,
+    alignment::{HorizontalAlignment, VerticalAlignment},
+    style::{HeightMode, TextBoxStyleBuilder, VerticalOverdraw},
+};
+
+use crate::{
+    InputEvent, Key, Response, StateFlags, StateManager, ThemedWidget, Widget,
+    i18n::Text,
+    layout::Place,
+    screen::Element,
+    themes::{Style, Theme},
+    widgets::{
+        frame::Frame,
+        popup::{List, step},
+    },
+};
+
+/// Shows the chosen option and opens a list of all options below it when
+/// tapped.
+///
+/// The open list is drawn as an [overlay](crate::Widget::overlay) over
+/// the widgets below and gets every pointer event: tapping an option
+/// chooses it, tapping anywhere else closes the list. While focused,
+/// enter opens the list, up and down move through it, enter chooses and
+/// escape closes it. With the list closed, up and down choose the
+/// previous or next option right away, so a rotary encoder can step
+/// through the options.
+///
+/// ```
+/// # use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+/// # use embui::{InputEvent, Widget, widgets::Dropdown};
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Message {
+///     Baud(usize),
+/// }
+///
+/// let mut baud: Dropdown<Message, Rgb888, 3> = Dropdown::new(
+///     Rectangle::new(Point::zero(), Size::new(80, 20)),
+///     ["9600", "57600", "115200"],
+/// )
+/// .on_select(Message::Baud);
+///
+/// let field = Point::new(10, 10);
+/// baud.handle_event(InputEvent::Touch(field));
+/// baud.handle_event(InputEvent::TouchRelease(field));
+/// assert!(baud.is_open());
+///
+/// // The third option is the third row below the field
+/// let option = Point::new(10, 70);
+/// baud.handle_event(InputEvent::Touch(option));
+/// let response = baud.handle_event(InputEvent::TouchRelease(option));
+/// assert_eq!(response.message(), Some(Message::Baud(2)));
+/// assert!(!baud.is_open());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Dropdown<'a, M, C, const N: usize>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    options: [Text<'a>; N],
+    selected: usize,
+    open: bool,
+    highlighted: Option<usize>,
+    on_select: Option<fn(usize) -> M>,
+    bounds: Rectangle,
+    style: Style<C>,
+    state_manager: StateManager,
+}
+
+impl<'a, M, C, const N: usize> Dropdown<'a, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// A closed dropdown in `bounds` with the first option chosen
+    pub fn new(bounds: Rectangle, options: [impl Into<Text<'a>>; N]) -> Self {
+        Self {
+            options: options.map(Into::into),
+            selected: 0,
+            open: false,
+            highlighted: None,
+            on_select: None,
+            bounds,
+            style: Style::new(),
+            state_manager: StateManager::new(),
+        }
+    }
+
+    /// Send the message for the index of every option that is chosen
+    pub fn on_select(mut self, message: fn(usize) -> M) -> Self {
+        self.on_select = Some(message);
+        self
+    }
+    /// Override theme colours or font
+    pub fn with_style(mut self, style: Style<C>) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+    /// Choose an option without sending a message
+    pub fn set_selected(&mut self, index: usize) {
+        if index < N && index != self.selected {
+            self.selected = index;
+            self.state_manager.mark_dirty();
+        }
+    }
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+    pub fn close(&mut self) {
+        if self.open {
+            self.open = false;
+            self.state_manager.mark_dirty();
+        }
+    }
+
+    fn open(&mut self) {
+        self.open = true;
+        self.highlighted = Some(self.selected);
+        self.state_manager.mark_dirty();
+    }
+    /// One row per option, each as high as the dropdown, right below it
+    fn list(&self) -> List {
+        let size = self.bounds.size;
+        List {
+            bounds: Rectangle::new(
+                self.bounds.top_left + Point::new(0, size.height as i32),
+                Size::new(size.width, size.height * N as u32),
+            ),
+            rows: N,
+        }
+    }
+    fn highlight(&mut self, index: Option<usize>) {
+        if index != self.highlighted {
+            self.highlighted = index;
+            self.state_manager.mark_dirty();
+        }
+    }
+    fn choose(&mut self, index: usize) -> Response<M> {
+        self.close();
+        self.selected = index;
+        self.state_manager.mark_dirty();
+        Response::Consumed(self.to_message())
+    }
+}
+
+impl<M, C, const N: usize> Widget<M> for Dropdown<'_, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// The select message for the chosen option
+    fn to_message(&self) -> Option<M> {
+        self.on_select.map(|message| message(self.selected))
+    }
+    fn handle_event(&mut self, event: InputEvent) -> Response<M> {
+        let state = &mut self.state_manager;
+        if !state.is_enabled() || !state.is_visible() {
+            return Response::NotChanged;
+        }
+        let inside = match event {
+            InputEvent::Touch(point)
+            | InputEvent::TouchMove(point)
+            | InputEvent::TouchRelease(point)
+            | InputEvent::MouseMove(point)
+            | InputEvent::MouseEnter(point)
+            | InputEvent::MouseLeave(point)
+            | InputEvent::MouseDown(point)
+            | InputEvent::MouseUp(point) => self.bounds.contains(point),
+            InputEvent::KeyPress(_) | InputEvent::Key(_) => false,
+        };
+        let pointer = !matches!(event, InputEvent::KeyPress(_) | InputEvent::Key(_));
+        let was_pressed = state.is_pressed();
+        // While open the whole screen belongs to the list, like a popup menu
+        let state_changed = state.handle_event(event, inside || (self.open && pointer));
+        let focused = state.is_focused();
+
+        if self.open {
+            let list = self.list();
+            match event {
+                InputEvent::Touch(point)
+                | InputEvent::TouchMove(point)
+                | InputEvent::MouseDown(point)
+                | InputEvent::MouseMove(point) => self.highlight(list.row_at(point)),
+                InputEvent::TouchRelease(point) | InputEvent::MouseUp(point) if was_pressed => {
+                    match list.row_at(point) {
+                        Some(index) => return self.choose(index),
+                        None => self.close(),
+                    }
+                }
+                InputEvent::Key(Key::Up) => self.highlight(step(self.highlighted, N, false)),
+                InputEvent::Key(Key::Down) => self.highlight(step(self.highlighted, N, true)),
+                InputEvent::Key(Key::Enter) => match self.highlighted {
+                    Some(index) => return self.choose(index),
+                    None => self.close(),
+                },
+                InputEvent::Key(Key::Escape) => self.close(),
+                _ => {}
+            }
+            return Response::Consumed(None);
+        }
+
+        match event {
+            InputEvent::TouchRelease(_) | InputEvent::MouseUp(_) if was_pressed && inside => {
+                self.open();
+                Response::Consumed(None)
+            }
+            InputEvent::Key(Key::Enter) if focused => {
+                self.open();
+                Response::Consumed(None)
+            }
+            InputEvent::Key(Key::Up) if focused && self.selected > 0 => {
+                self.choose(self.selected - 1)
+            }
+            InputEvent::Key(Key::Down) if focused && self.selected + 1 < N => {
+                self.choose(self.selected + 1)
+            }
+            _ if was_pressed || self.state_manager.is_pressed() => Response::Consumed(None),
+            _ if state_changed => Response::Changed(None),
+            _ => Response::NotChanged,
+        }
+    }
+    fn overlay(&self) -> Option<Rectangle> {
+        self.open.then(|| self.list().bounds)
+    }
+    fn get_state_manager(&self) -> &StateManager {
+        &self.state_manager
+    }
+    fn get_state_manager_mut(&mut self) -> &mut StateManager {
+        &mut self.state_manager
+    }
+}
+
+impl<D, T, C, M, const N: usize> ThemedWidget<D, T, C> for Dropdown<'_, M, C, N>
+where
+    C: PixelColor + Default + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    M: Copy,
+{
+    fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), <D as DrawTarget>::Error> {
+        // Shown selected while the list is open
+        let mut flags = self.state_flags();
+        flags.set(StateFlags::SELECTED, self.open);
+        let style = self.style.resolve(theme, flags);
+        // Like buttons, leave room for shadows and the focus ring
+        Frame::themed(
+            self.bounds.offset(-(theme.spacing_xs() as i32)),
+            theme,
+            flags,
+            style.background,
+            style.border,
+        )
+        .draw(target)?;
+
+        // A small arrow pointing down at the right
+        let inner = self.bounds.offset(-(style.padding as i32));
+        let arrow = (self.bounds.size.height / 4).max(2) as i32;
+        let tip = Point::new(
+            inner.top_left.x + inner.size.width as i32 - arrow,
+            self.bounds.center().y + arrow / 2,
+        );
+        Triangle::new(
+            tip,
+            tip + Point::new(-arrow, -arrow),
+            tip + Point::new(arrow, -arrow),
+        )
+        .into_styled(PrimitiveStyle::with_fill(style.text))
+        .draw(target)?;
+
+        let alignment = match self.style.alignment {
+            Some(_) => style.alignment,
+            None => theme.direction().align(HorizontalAlignment::Left),
+        };
+        let textbox_style = TextBoxStyleBuilder::new()
+            .height_mode(HeightMode::Exact(VerticalOverdraw::Hidden))
+            .alignment(alignment)
+            .vertical_alignment(VerticalAlignment::Middle)
+            .build();
+        let label = Rectangle::new(
+            inner.top_left,
+            Size::new(
+                inner.size.width.saturating_sub(2 * arrow as u32 + 2),
+                inner.size.height,
+            ),
+        );
+        TextBox::with_textbox_style(
+            self.options[self.selected].resolve(theme),
+            label,
+            MonoTextStyle::new(style.font, style.text),
+            textbox_style,
+        )
+        .draw(target)?;
+        Ok(())
+    }
+    fn draw_overlay(&self, target: &mut D, theme: &T) -> Result<(), D::Error> {
+        if !self.open {
+            return Ok(());
+        }
+        self.list()
+            .draw(target, theme, &self.style, &self.options, self.highlighted)
+    }
+}
+
+impl<M, D, T, C, const N: usize> Element<M, D, T, C> for Dropdown<'_, M, C, N>
+where
+    M: Copy + Clone,
+    D: DrawTarget<Color = C>,
+    C: PixelColor + Default + From<Rgb888>,
+    T: Theme<C>,
+{
+}
+
+impl<M, C, const N: usize> Transform for Dropdown<'_, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn translate(&self, by: Point) -> Self {
+        let mut moved = self.clone();
+        moved.translate_mut(by);
+        moved
+    }
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.bounds.top_left += by;
+        self
+    }
+}
+
+impl<M, C, const N: usize> Place for Dropdown<'_, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn place(&mut self, bounds: Rectangle) {
+        self.bounds = bounds;
+        self.state_manager.mark_dirty();
+    }
+}
+
+impl<M, C, const N: usize> Dimensions for Dropdown<'_, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// The field showing the chosen option, without the list
+    fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::mock_display::MockDisplay;
+
+    use super::*;
+    use crate::themes::DefaultTheme;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Message {
+        Mode(usize),
+    }
+
+    fn dropdown() -> Dropdown<'static, Message, Rgb888, 3> {
+        Dropdown::new(
+            Rectangle::new(Point::zero(), Size::new(60, 16)),
+            ["Off", "On", "Auto"],
+        )
+        .on_select(Message::Mode)
+    }
+
+    #[test]
+    fn keys_step_and_open() {
+        let mut dropdown = dropdown();
+        let down = InputEvent::Key(Key::Down);
+        assert!(dropdown.handle_event(down).message().is_none());
+
+        // Closed, up and down choose right away
+        dropdown.get_state_manager_mut().set_focused(true);
+        assert_eq!(
+            dropdown.handle_event(down).message(),
+            Some(Message::Mode(1))
+        );
+
+        // Open, they only move the highlight until enter
+        dropdown.handle_event(InputEvent::Key(Key::Enter));
+        assert!(dropdown.is_open());
+        assert!(dropdown.handle_event(down).message().is_none());
+        assert!(dropdown.handle_event(down).message().is_none());
+        let response = dropdown.handle_event(InputEvent::Key(Key::Enter));
+        assert_eq!(response.message(), Some(Message::Mode(2)));
+        assert!(!dropdown.is_open());
+
+        dropdown.handle_event(InputEvent::Key(Key::Enter));
+        dropdown.handle_event(InputEvent::Key(Key::Up));
+        dropdown.handle_event(InputEvent::Key(Key::Escape));
+        assert!(!dropdown.is_open());
+        assert_eq!(dropdown.selected(), 2);
+    }
+
+    #[test]
+    fn tap_outside_closes() {
+        let mut dropdown = dropdown();
+        dropdown.handle_event(InputEvent::MouseDown(Point::new(5, 5)));
+        dropdown.handle_event(InputEvent::MouseUp(Point::new(5, 5)));
+        assert!(dropdown.overlay().is_some());
+
+        let outside = Point::new(62, 60);
+        dropdown.handle_event(InputEvent::MouseDown(outside));
+        let response = dropdown.handle_event(InputEvent::MouseUp(outside));
+        assert!(response.message().is_none());
+        assert!(dropdown.overlay().is_none());
+        assert_eq!(dropdown.selected(), 0);
+    }
+
+    #[test]
+    fn list_is_drawn_as_overlay() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let mut dropdown = dropdown();
+        dropdown.handle_event(InputEvent::Touch(Point::new(5, 5)));
+        dropdown.handle_event(InputEvent::TouchRelease(Point::new(5, 5)));
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        display.set_allow_out_of_bounds_drawing(true);
+        dropdown.draw_with_theme(&mut display, &theme).unwrap();
+        assert_eq!(display.get_pixel(Point::new(30, 40)), None);
+
+        dropdown.draw_overlay(&mut display, &theme).unwrap();
+        assert_eq!(
+            display.get_pixel(Point::new(30, 40)),
+            Some(theme.panel_background_color())
+        );
+        // The chosen option is highlighted
+        let selected = dropdown.style.resolve(&theme, StateFlags::SELECTED);
+        assert_eq!(
+            display.get_pixel(Point::new(55, 24)),
+            Some(selected.background)
+        );
+    }
+}
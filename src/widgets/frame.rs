@@ -0,0 +1,288 @@
+use embedded_graphics::{
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{
+        CornerRadii, Line, PrimitiveStyle, PrimitiveStyleBuilder, Rectangle, RoundedRectangle,
+        StrokeAlignment, StyledDrawable,
+    },
+};
+
+use crate::{StateFlags, Theme, WidgetState};
+
+/// How the inside of a [`Frame`] is painted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fill<C> {
+    Solid(C),
+    /// Vertical gradient from the top colour to the bottom colour. The
+    /// colours are blended in RGB, so this only looks right on RGB targets.
+    Gradient(Rgb888, Rgb888),
+}
+
+/// A 3D edge drawn just inside the border.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Bevel {
+    #[default]
+    None,
+    /// Light top-left edge, dark bottom-right edge
+    Raised,
+    /// Dark top-left edge, light bottom-right edge
+    Pressed,
+}
+
+/// The background shape shared by all widgets: a rounded rectangle with an
+/// optional fill, border, drop shadow and bevel.
+///
+/// Widgets normally build it with [`Frame::themed`], which reads the
+/// button knobs of the [`Theme`], so every widget picks up corner radii,
+/// border widths and shadows the same way.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame<C> {
+    bounds: Rectangle,
+    radius: u32,
+    fill: Option<Fill<C>>,
+    border: Option<(C, u32)>,
+    shadow: Option<(Point, C)>,
+    bevel: Bevel,
+    bevel_colors: Option<(C, C)>,
+    focus_ring: Option<(C, u32)>,
+}
+
+impl<C> Frame<C>
+where
+    C: PixelColor + From<Rgb888>,
+{
+    /// An invisible frame, add parts with the builder methods
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            bounds,
+            radius: 0,
+            fill: None,
+            border: None,
+            shadow: None,
+            bevel: Bevel::None,
+            bevel_colors: None,
+            focus_ring: None,
+        }
+    }
+
+    /// A frame for an interactive widget in `state`, styled with the theme's
+    /// button knobs. Pressed widgets sink: their shadow is dropped and the
+    /// bevel is inverted. Focused widgets get a focus ring, also while
+    /// they are hovered or pressed.
+    pub fn themed<T>(
+        bounds: Rectangle,
+        theme: &T,
+        state: impl Into<StateFlags>,
+        background: C,
+        border: C,
+    ) -> Self
+    where
+        T: Theme<C>,
+    {
+        let flags = state.into();
+        let state = flags.state();
+        let pressed = state == WidgetState::Pressed;
+        let mut frame = Self::new(bounds)
+            .with_radius(theme.button_corner_radius())
+            .with_border(border, theme.button_border_width());
+        frame = match theme.button_gradient(state) {
+            Some((top, bottom)) => frame.with_gradient(top, bottom),
+            None => frame.with_fill(background),
+        };
+        if let Some(offset) = theme.button_shadow_offset().filter(|_| !pressed) {
+            frame = frame.with_shadow(offset, theme.shadow_color());
+        }
+        if theme.button_bevel() {
+            let bevel = if pressed {
+                Bevel::Pressed
+            } else {
+                Bevel::Raised
+            };
+            frame = frame.with_bevel(bevel, theme.bevel_light_color(), theme.bevel_dark_color());
+        }
+        if state != WidgetState::Disabled && flags.contains(StateFlags::FOCUSED) {
+            frame = frame.with_focus_ring(theme.focus_ring_color(), theme.focus_ring_width());
+        }
+        frame
+    }
+
+    pub fn with_radius(mut self, radius: u32) -> Self {
+        self.radius = radius;
+        self
+    }
+    pub fn with_fill(mut self, color: C) -> Self {
+        self.fill = Some(Fill::Solid(color));
+        self
+    }
+    pub fn with_gradient(mut self, top: Rgb888, bottom: Rgb888) -> Self {
+        self.fill = Some(Fill::Gradient(top, bottom));
+        self
+    }
+    /// A border of `width` pixels drawn inside the bounds
+    pub fn with_border(mut self, color: C, width: u32) -> Self {
+        self.border = (width > 0).then_some((color, width));
+        self
+    }
+    /// A copy of the shape offset by `offset`, drawn underneath
+    pub fn with_shadow(mut self, offset: Point, color: C) -> Self {
+        self.shadow = Some((offset, color));
+        self
+    }
+    pub fn with_bevel(mut self, bevel: Bevel, light: C, dark: C) -> Self {
+        self.bevel = bevel;
+        self.bevel_colors = Some((light, dark));
+        self
+    }
+
+    /// A second outline one pixel inside the border. It stays inside the
+    /// bounds, so redrawing the frame without it erases it.
+    pub fn with_focus_ring(mut self, color: C, width: u32) -> Self {
+        self.focus_ring = (width > 0).then_some((color, width));
+        self
+    }
+
+    fn shape(&self, bounds: Rectangle) -> RoundedRectangle {
+        self.shape_with_radius(bounds, self.radius)
+    }
+    fn shape_with_radius(&self, bounds: Rectangle, radius: u32) -> RoundedRectangle {
+        let radius = Size::new(radius, radius);
+        RoundedRectangle::new(bounds, CornerRadii::new(radius))
+    }
+
+    pub fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        if let Some((offset, color)) = self.shadow {
+            self.shape(self.bounds.translate(offset))
+                .draw_styled(&PrimitiveStyle::with_fill(color), target)?;
+        }
+
+        let shape = self.shape(self.bounds);
+        let mut style = PrimitiveStyleBuilder::new().stroke_alignment(StrokeAlignment::Inside);
+        match self.fill {
+            Some(Fill::Solid(color)) => style = style.fill_color(color),
+            Some(Fill::Gradient(top, bottom)) => {
+                let top_y = self.bounds.top_left.y;
+                let steps = self.bounds.size.height.saturating_sub(1).max(1);
+                let pixels = shape.points().map(|p| {
+                    let t = (p.y - top_y) as u32;
+                    Pixel(p, C::from(blend(top, bottom, t, steps)))
+                });
+                target.draw_iter(pixels)?;
+            }
+            None => {}
+        }
+        if let Some((color, width)) = self.border {
+            style = style.stroke_color(color).stroke_width(width);
+        }
+        shape.draw_styled(&style.build(), target)?;
+
+        if let Some((color, width)) = self.focus_ring {
+            let inset = self.border.map_or(0, |(_, width)| width) + 1;
+            self.shape_with_radius(
+                self.bounds.offset(-(inset as i32)),
+                self.radius.saturating_sub(inset),
+            )
+            .draw_styled(
+                &PrimitiveStyleBuilder::new()
+                    .stroke_color(color)
+                    .stroke_width(width)
+                    .stroke_alignment(StrokeAlignment::Inside)
+                    .build(),
+                target,
+            )?;
+        }
+
+        if let (Some((light, dark)), Some(bottom_right)) =
+            (self.bevel_colors, self.bounds.bottom_right())
+        {
+            let inset = self.border.map_or(0, |(_, width)| width) as i32;
+            let (top_left_color, bottom_right_color) = match self.bevel {
+                Bevel::None => return Ok(()),
+                Bevel::Raised => (light, dark),
+                Bevel::Pressed => (dark, light),
+            };
+            // Keep the bevel on the straight edges, clear of the corners
+            let r = (self.radius as i32 - inset).max(0);
+            let left = self.bounds.top_left.x + inset;
+            let top = self.bounds.top_left.y + inset;
+            let right = bottom_right.x - inset;
+            let bottom = bottom_right.y - inset;
+            let light_style = PrimitiveStyle::with_stroke(top_left_color, 1);
+            let dark_style = PrimitiveStyle::with_stroke(bottom_right_color, 1);
+            Line::new(Point::new(left + r, top), Point::new(right - r, top))
+                .draw_styled(&light_style, target)?;
+            Line::new(Point::new(left, top + r), Point::new(left, bottom - r))
+                .draw_styled(&light_style, target)?;
+            Line::new(Point::new(left + r, bottom), Point::new(right - r, bottom))
+                .draw_styled(&dark_style, target)?;
+            Line::new(Point::new(right, top + r), Point::new(right, bottom - r))
+                .draw_styled(&dark_style, target)?;
+        }
+        Ok(())
+    }
+}
+
+/// Linear blend from `from` to `to` at `step` of `steps`
+fn blend(from: Rgb888, to: Rgb888, step: u32, steps: u32) -> Rgb888 {
+    let mix = |a: u8, b: u8| {
+        let (a, b) = (a as i32, b as i32);
+        (a + (b - a) * step.min(steps) as i32 / steps as i32) as u8
+    };
+    Rgb888::new(
+        mix(from.r(), to.r()),
+        mix(from.g(), to.g()),
+        mix(from.b(), to.b()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::mock_display::MockDisplay;
+
+    use super::*;
+    use crate::themes::DefaultTheme;
+
+    #[test]
+    fn gradient_blends_top_to_bottom() {
+        let mut display = MockDisplay::<Rgb888>::new();
+        Frame::new(Rectangle::new(Point::zero(), Size::new(2, 3)))
+            .with_gradient(Rgb888::BLACK, Rgb888::new(200, 100, 0))
+            .draw(&mut display)
+            .unwrap();
+        assert_eq!(display.get_pixel(Point::new(0, 0)), Some(Rgb888::BLACK));
+        assert_eq!(
+            display.get_pixel(Point::new(1, 1)),
+            Some(Rgb888::new(100, 50, 0))
+        );
+        assert_eq!(
+            display.get_pixel(Point::new(0, 2)),
+            Some(Rgb888::new(200, 100, 0))
+        );
+    }
+
+    #[test]
+    fn themed_border_uses_border_width() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let mut display = MockDisplay::<Rgb888>::new();
+        Frame::themed(
+            Rectangle::new(Point::zero(), Size::new(6, 6)),
+            &theme,
+            WidgetState::Normal,
+            theme.button_normal_bg(),
+            theme.button_normal_border(),
+        )
+        .draw(&mut display)
+        .unwrap();
+        assert_eq!(theme.button_border_width(), 1);
+        assert_eq!(
+            display.get_pixel(Point::new(0, 0)),
+            Some(theme.button_normal_border())
+        );
+        assert_eq!(
+            display.get_pixel(Point::new(1, 1)),
+            Some(theme.button_normal_bg())
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use core::{fmt::Write, marker::PhantomData};
+
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{Arc, PrimitiveStyle, Sector, StyledDrawable},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+use crate::{
+    StateManager, Theme, ThemedWidget, Widget,
+    binding::{Binding, Bound},
+    widgets::{chart::Thresholds, frame::Frame},
+};
+
+/// A circular dial showing `value` within `min..=max`.
+///
+/// The scale is an arc that by default opens towards the bottom, with
+/// optional coloured zones from [`Thresholds`] drawn along its outer edge and
+/// a needle pointing at the current value.
+#[derive(Clone, Widget)]
+pub struct Gauge<'a, M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    ph: PhantomData<M>,
+    #[binding]
+    value: Bound<'a, i32>,
+    min: i32,
+    max: i32,
+    start: Angle,
+    sweep: Angle,
+    zones: Option<Thresholds>,
+    show_value: bool,
+    color: Option<C>,
+    #[position]
+    pos: Point,
+    #[size]
+    size: Size,
+    #[state]
+    state_manager: StateManager,
+}
+
+impl<'a, M, C> Gauge<'a, M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// A 270° gauge going from 0 to 100
+    pub fn new(pos: Point, size: Size) -> Self {
+        Self {
+            ph: PhantomData,
+            value: Bound::new(0),
+            min: 0,
+            max: 100,
+            start: Angle::from_degrees(135.0),
+            sweep: Angle::from_degrees(270.0),
+            zones: None,
+            show_value: true,
+            color: None,
+            pos,
+            size,
+            state_manager: StateManager::read_only(),
+        }
+    }
+
+    /// The ends of the scale, in either order
+    pub fn with_range(mut self, min: i32, max: i32) -> Self {
+        self.min = min.min(max);
+        self.max = min.max(max);
+        self
+    }
+    /// Angles are measured clockwise from the positive X axis
+    pub fn with_angles(mut self, start: Angle, sweep: Angle) -> Self {
+        self.start = start;
+        self.sweep = sweep;
+        self
+    }
+    /// Draw success, warning and error zones along the scale
+    pub fn with_zones(mut self, zones: Thresholds) -> Self {
+        self.zones = Some(zones);
+        self
+    }
+    pub fn with_value_label(mut self, show: bool) -> Self {
+        self.show_value = show;
+        self
+    }
+    pub fn with_color(mut self, color: C) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Show a value owned by the model instead of the widget
+    pub fn bind(mut self, binding: impl Into<Binding<'a, i32>>) -> Self {
+        self.value.bind(binding.into());
+        self
+    }
+    /// Set the value, writing through to a bound [`Signal`](crate::Signal)
+    pub fn set(&mut self, value: i32) {
+        self.value.set(value);
+    }
+    pub fn get(&self) -> i32 {
+        self.value.get()
+    }
+
+    /// Angle at which `value` sits on the scale
+    fn angle(&self, value: i32) -> Angle {
+        let span = (self.max as f32 - self.min as f32).max(1.0);
+        let offset = (value.clamp(self.min, self.max) as f32 - self.min as f32) / span;
+        Angle::from_degrees(self.start.to_degrees() + self.sweep.to_degrees() * offset)
+    }
+
+    fn dial(&self) -> (Point, u32) {
+        let bounds = self.bounding_box();
+        (bounds.center(), self.size.width.min(self.size.height))
+    }
+}
+
+impl<D, T, C, M> ThemedWidget<D, T, C> for Gauge<'_, M, C>
+where
+    C: PixelColor + Default + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    M: Copy,
+{
+    fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), <D as DrawTarget>::Error> {
+        let (center, diameter) = self.dial();
+        let band = (diameter / 8).max(2);
+        let zone_band = if self.zones.is_some() { band / 2 } else { 0 };
+
+        Frame::new(self.bounding_box())
+            .with_fill(theme.background_color())
+            .draw(target)?;
+
+        // Zones sit on the outer edge of the scale
+        if let Some(zones) = self.zones {
+            let ring = Arc::with_center(
+                center,
+                diameter.saturating_sub(zone_band),
+                self.start,
+                self.sweep,
+            );
+            let (lower, upper) = (self.min, self.max);
+            let (first, second) = if zones.warning <= zones.error {
+                (zones.warning, zones.error)
+            } else {
+                (zones.error, zones.warning)
+            };
+            for (from, to) in [(lower, first), (first, second), (second, upper)] {
+                let (from, to) = (from.clamp(lower, upper), to.clamp(lower, upper));
+                if from >= to {
+                    continue;
+                }
+                let start = self.angle(from);
+                let zone = Arc {
+                    angle_start: start,
+                    angle_sweep: self.angle(to) - start,
+                    ..ring
+                };
+                zone.draw_styled(
+                    &PrimitiveStyle::with_stroke(
+                        zones.color(theme, ((from as i64 + to as i64) / 2) as i32),
+                        zone_band,
+                    ),
+                    target,
+                )?;
+            }
+        }
+
+        let track = Arc::with_center(
+            center,
+            diameter.saturating_sub(2 * zone_band + band),
+            self.start,
+            self.sweep,
+        );
+        track.draw_styled(
+            &PrimitiveStyle::with_stroke(theme.meter_track_color(), band),
+            target,
+        )?;
+
+        let fill = match self.zones {
+            Some(zones) => zones.color(theme, self.value.get()),
+            None => self.color.unwrap_or_else(|| theme.meter_fill_color()),
+        };
+        let value_arc = Arc {
+            angle_sweep: self.angle(self.value.get()) - self.start,
+            ..track
+        };
+        value_arc.draw_styled(&PrimitiveStyle::with_stroke(fill, band), target)?;
+
+        // The needle is a narrow sector from the centre to the scale
+        let needle_width = Angle::from_degrees(6.0);
+        Sector::with_center(
+            center,
+            diameter.saturating_sub(2 * (zone_band + band)),
+            self.angle(self.value.get()) - Angle::from_degrees(needle_width.to_degrees() / 2.0),
+            needle_width,
+        )
+        .draw_styled(
+            &PrimitiveStyle::with_fill(theme.meter_needle_color()),
+            target,
+        )?;
+
+        if self.show_value {
+            let mut text: heapless::String<11> = heapless::String::new();
+            write!(&mut text, "{}", self.value.get()).unwrap();
+            let text_style = TextStyleBuilder::new()
+                .alignment(Alignment::Center)
+                .baseline(Baseline::Top)
+                .build();
+            Text::with_text_style(
+                text.as_str(),
+                center + Point::new(0, (diameter / 4) as i32),
+                MonoTextStyle::new(theme.normal_font(), theme.text_primary()),
+                text_style,
+            )
+            .draw(target)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::mock_display::MockDisplay;
+
+    use super::*;
+    use crate::themes::DefaultTheme;
+
+    #[derive(Clone, Copy)]
+    enum Message {}
+
+    #[test]
+    fn value_is_clamped_to_the_scale() {
+        let gauge: Gauge<Message, Rgb888> =
+            Gauge::new(Point::zero(), Size::new(60, 60)).with_range(-20, 20);
+        assert_eq!(gauge.angle(-50).to_degrees(), 135.0);
+        assert_eq!(gauge.angle(-20).to_degrees(), 135.0);
+        assert_eq!(gauge.angle(0).to_degrees(), 270.0);
+        assert_eq!(gauge.angle(20).to_degrees(), 405.0);
+        assert_eq!(gauge.angle(i32::MAX).to_degrees(), 405.0);
+    }
+
+    #[test]
+    fn reversed_and_huge_ranges_draw() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let reversed: Gauge<Message, Rgb888> = Gauge::new(Point::zero(), Size::new(60, 60))
+            .with_range(20, -20)
+            .with_value_label(false);
+        assert_eq!(reversed.angle(20).to_degrees(), 405.0);
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        reversed.draw_with_theme(&mut display, &theme).unwrap();
+
+        let huge: Gauge<Message, Rgb888> = Gauge::new(Point::zero(), Size::new(60, 60))
+            .with_range(i32::MIN, i32::MAX)
+            .with_zones(Thresholds::new(i32::MIN + 1, i32::MAX - 1))
+            .with_value_label(false);
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        huge.draw_with_theme(&mut display, &theme).unwrap();
+    }
+
+    #[test]
+    fn zones_colour_the_scale_and_value() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let mut gauge: Gauge<Message, Rgb888> = Gauge::new(Point::zero(), Size::new(60, 60))
+            .with_zones(Thresholds::new(60, 80))
+            .with_value_label(false);
+        gauge.set(70);
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        gauge.draw_with_theme(&mut display, &theme).unwrap();
+
+        let (center, _) = gauge.dial();
+        // Right of the centre is about 83, in the error zone but past the value
+        assert_eq!(
+            display.get_pixel(center + Point::new(28, 0)),
+            Some(theme.error_color())
+        );
+        assert_eq!(
+            display.get_pixel(center + Point::new(23, 0)),
+            Some(theme.meter_track_color())
+        );
+        // Left of the centre is about 17, filled in the colour of the value
+        assert_eq!(
+            display.get_pixel(center - Point::new(28, 0)),
+            Some(theme.success_color())
+        );
+        assert_eq!(
+            display.get_pixel(center - Point::new(23, 0)),
+            Some(theme.warning_color())
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use core::marker::PhantomData;
+
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+
+use crate::{
+    StateManager, Theme, ThemedWidget, Widget, WidgetState, layout::Place, screen::Element,
+};
+
+/// A packed 1-bit glyph that is drawn in a single tint colour.
+///
+/// Pixels are stored row by row, most significant bit first, and every row
+/// starts on a new byte. This is the same layout as an
+/// `ImageRaw<BinaryColor>`, so existing glyph data can be reused as is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Icon {
+    width: u32,
+    height: u32,
+    data: &'static [u8],
+}
+
+impl Icon {
+    pub const fn new(width: u32, height: u32, data: &'static [u8]) -> Self {
+        assert!(data.len() >= (width.div_ceil(8) * height) as usize);
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    fn is_set(&self, x: u32, y: u32) -> bool {
+        let stride = self.width.div_ceil(8);
+        let byte = self.data[(y * stride + x / 8) as usize];
+        byte & (0x80 >> (x % 8)) != 0
+    }
+
+    /// Draw the set pixels in `color`, leaving the others untouched
+    pub fn draw_tinted<D, C>(
+        &self,
+        top_left: Point,
+        color: C,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: PixelColor,
+    {
+        let pixels = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.is_set(x, y))
+            .map(|(x, y)| Pixel(top_left + Point::new(x as i32, y as i32), color));
+        target.draw_iter(pixels)
+    }
+}
+
+/// A widget showing a single [`Icon`], tinted with the theme's text colour
+/// unless a colour is given.
+#[derive(Clone)]
+pub struct IconView<M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    ph: PhantomData<M>,
+    icon: Icon,
+    color: Option<C>,
+    pos: Point,
+    state_manager: StateManager,
+}
+
+impl<M, C> IconView<M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    pub fn new(icon: Icon, pos: Point) -> Self {
+        Self {
+            ph: PhantomData,
+            icon,
+            color: None,
+            pos,
+            state_manager: StateManager::read_only(),
+        }
+    }
+    pub fn with_color(mut self, color: C) -> Self {
+        self.color = Some(color);
+        self
+    }
+    /// Swap the icon, e.g. for a battery or signal strength indicator
+    pub fn set(&mut self, icon: Icon) {
+        self.icon = icon;
+        self.state_manager.mark_dirty();
+    }
+}
+
+impl<M, C> Widget<M> for IconView<M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn to_message(&self) -> Option<M> {
+        None
+    }
+    fn get_state_manager(&self) -> &StateManager {
+        &self.state_manager
+    }
+    fn get_state_manager_mut(&mut self) -> &mut StateManager {
+        &mut self.state_manager
+    }
+}
+
+impl<D, T, C, M> ThemedWidget<D, T, C> for IconView<M, C>
+where
+    C: PixelColor + Default + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    M: Copy,
+{
+    fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), <D as DrawTarget>::Error> {
+        let color = match Widget::get_state(self) {
+            WidgetState::Disabled => theme.text_disabled(),
+            _ => self.color.unwrap_or_else(|| theme.text_primary()),
+        };
+        self.icon.draw_tinted(self.pos, color, target)
+    }
+}
+
+impl<M, D, T, C> Element<M, D, T, C> for IconView<M, C>
+where
+    M: Copy + Clone,
+    D: DrawTarget<Color = C>,
+    C: PixelColor + Default + From<Rgb888>,
+    T: Theme<C>,
+{
+}
+
+impl<M, C> Transform for IconView<M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn translate(&self, by: Point) -> Self {
+        let mut new_view = self.clone();
+        new_view.pos += by;
+        new_view
+    }
+
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.pos += by;
+        self
+    }
+}
+impl<M, C> Place for IconView<M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// Centre the icon in `bounds`
+    fn place(&mut self, bounds: Rectangle) {
+        self.pos = bounds.center() - self.icon.size() / 2;
+        self.state_manager.mark_dirty();
+    }
+}
+
+impl<M, C> Dimensions for IconView<M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(self.pos, self.icon.size())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    use super::*;
+
+    #[test]
+    fn icon_draws_set_bits_only() {
+        const ARROW: Icon = Icon::new(3, 2, &[0b0100_0000, 0b1010_0000]);
+        let mut display = MockDisplay::<BinaryColor>::new();
+        ARROW
+            .draw_tinted(Point::new(1, 1), BinaryColor::On, &mut display)
+            .unwrap();
+        display.assert_pattern(&[
+            "    ", //
+            "  # ", //
+            " # #", //
+        ]);
+    }
+}
@@ -0,0 +1,215 @@
+//! A small set of 12x12 monochrome icons for common actions and status.
+//!
+//! All icons are tinted at draw time, so they follow the theme's text colour
+//! when used in a [`Button`](crate::widgets::Button) or an
+//! [`IconView`](crate::widgets::IconView).
+use super::icon::Icon;
+
+pub const BACK: Icon = Icon::new(
+    12,
+    12,
+    &[
+        0b00000000, 0b00000000, // ............
+        0b00000100, 0b00000000, // .....#......
+        0b00001100, 0b00000000, // ....##......
+        0b00011100, 0b00000000, // ...###......
+        0b00111111, 0b11110000, // ..##########
+        0b01111111, 0b11110000, // .###########
+        0b01111111, 0b11110000, // .###########
+        0b00111111, 0b11110000, // ..##########
+        0b00011100, 0b00000000, // ...###......
+        0b00001100, 0b00000000, // ....##......
+        0b00000100, 0b00000000, // .....#......
+        0b00000000, 0b00000000, // ............
+    ],
+);
+
+pub const HOME: Icon = Icon::new(
+    12,
+    12,
+    &[
+        0b00000110, 0b00000000, // .....##.....
+        0b00001111, 0b00000000, // ....####....
+        0b00011001, 0b10000000, // ...##..##...
+        0b00110000, 0b11000000, // ..##....##..
+        0b01100000, 0b01100000, // .##......##.
+        0b11000000, 0b00110000, // ##........##
+        0b01001111, 0b00100000, // .#..####..#.
+        0b01001001, 0b00100000, // .#..#..#..#.
+        0b01001001, 0b00100000, // .#..#..#..#.
+        0b01001001, 0b00100000, // .#..#..#..#.
+        0b01111001, 0b11100000, // .####..####.
+        0b00000000, 0b00000000, // ............
+    ],
+);
+
+pub const SETTINGS: Icon = Icon::new(
+    12,
+    12,
+    &[
+        0b00000110, 0b00000000, // .....##.....
+        0b00100110, 0b01000000, // ..#..##..#..
+        0b01111111, 0b11100000, // .##########.
+        0b00111001, 0b11000000, // ..###..###..
+        0b00110000, 0b11000000, // ..##....##..
+        0b11110000, 0b11110000, // ####....####
+        0b11110000, 0b11110000, // ####....####
+        0b00110000, 0b11000000, // ..##....##..
+        0b00111001, 0b11000000, // ..###..###..
+        0b01111111, 0b11100000, // .##########.
+        0b00100110, 0b01000000, // ..#..##..#..
+        0b00000110, 0b00000000, // .....##.....
+    ],
+);
+
+pub const WIFI: Icon = Icon::new(
+    12,
+    12,
+    &[
+        0b00000000, 0b00000000, // ............
+        0b00011111, 0b10000000, // ...######...
+        0b01100000, 0b01100000, // .##......##.
+        0b10001111, 0b00010000, // #...####...#
+        0b00110000, 0b11000000, // ..##....##..
+        0b01000110, 0b00100000, // .#...##...#.
+        0b00001001, 0b00000000, // ....#..#....
+        0b00000000, 0b00000000, // ............
+        0b00000110, 0b00000000, // .....##.....
+        0b00000110, 0b00000000, // .....##.....
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+    ],
+);
+
+pub const BATTERY: Icon = Icon::new(
+    12,
+    12,
+    &[
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b11111111, 0b11000000, // ##########..
+        0b10000000, 0b01000000, // #........#..
+        0b10111111, 0b01110000, // #.######.###
+        0b10111111, 0b01110000, // #.######.###
+        0b10111111, 0b01110000, // #.######.###
+        0b10000000, 0b01000000, // #........#..
+        0b11111111, 0b11000000, // ##########..
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+    ],
+);
+
+pub const BATTERY_LOW: Icon = Icon::new(
+    12,
+    12,
+    &[
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b11111111, 0b11000000, // ##########..
+        0b10000000, 0b01000000, // #........#..
+        0b10110000, 0b01110000, // #.##.....###
+        0b10110000, 0b01110000, // #.##.....###
+        0b10110000, 0b01110000, // #.##.....###
+        0b10000000, 0b01000000, // #........#..
+        0b11111111, 0b11000000, // ##########..
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+    ],
+);
+
+pub const CHECK: Icon = Icon::new(
+    12,
+    12,
+    &[
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00110000, // ..........##
+        0b00000000, 0b01100000, // .........##.
+        0b00000000, 0b11000000, // ........##..
+        0b11000001, 0b10000000, // ##.....##...
+        0b01100011, 0b00000000, // .##...##....
+        0b00110110, 0b00000000, // ..##.##.....
+        0b00011100, 0b00000000, // ...###......
+        0b00001000, 0b00000000, // ....#.......
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+    ],
+);
+
+pub const CLOSE: Icon = Icon::new(
+    12,
+    12,
+    &[
+        0b00000000, 0b00000000, // ............
+        0b01100000, 0b01100000, // .##......##.
+        0b00110000, 0b11000000, // ..##....##..
+        0b00011001, 0b10000000, // ...##..##...
+        0b00001111, 0b00000000, // ....####....
+        0b00000110, 0b00000000, // .....##.....
+        0b00001111, 0b00000000, // ....####....
+        0b00011001, 0b10000000, // ...##..##...
+        0b00110000, 0b11000000, // ..##....##..
+        0b01100000, 0b01100000, // .##......##.
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+    ],
+);
+
+pub const PLUS: Icon = Icon::new(
+    12,
+    12,
+    &[
+        0b00000000, 0b00000000, // ............
+        0b00000110, 0b00000000, // .....##.....
+        0b00000110, 0b00000000, // .....##.....
+        0b00000110, 0b00000000, // .....##.....
+        0b00000110, 0b00000000, // .....##.....
+        0b01111111, 0b11100000, // .##########.
+        0b01111111, 0b11100000, // .##########.
+        0b00000110, 0b00000000, // .....##.....
+        0b00000110, 0b00000000, // .....##.....
+        0b00000110, 0b00000000, // .....##.....
+        0b00000110, 0b00000000, // .....##.....
+        0b00000000, 0b00000000, // ............
+    ],
+);
+
+pub const MINUS: Icon = Icon::new(
+    12,
+    12,
+    &[
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b01111111, 0b11100000, // .##########.
+        0b01111111, 0b11100000, // .##########.
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+    ],
+);
+
+pub const MENU: Icon = Icon::new(
+    12,
+    12,
+    &[
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+        0b01111111, 0b11100000, // .##########.
+        0b01111111, 0b11100000, // .##########.
+        0b00000000, 0b00000000, // ............
+        0b01111111, 0b11100000, // .##########.
+        0b01111111, 0b11100000, // .##########.
+        0b00000000, 0b00000000, // ............
+        0b01111111, 0b11100000, // .##########.
+        0b01111111, 0b11100000, // .##########.
+        0b00000000, 0b00000000, // ............
+        0b00000000, 0b00000000, // ............
+    ],
+);
@@ -0,0 +1,181 @@
+use core::marker::PhantomData;
+
+use embedded_graphics::{
+    image::ImageDrawable, pixelcolor::Rgb888, prelude::*, primitives::Rectangle,
+};
+
+use crate::{StateManager, Theme, ThemedWidget, Widget, layout::Place, screen::Element};
+
+/// A widget showing a full colour image.
+///
+/// Any [`ImageDrawable`] with the display's colour type works, e.g.
+/// `tinybmp::Bmp`, `tinytga::Tga`, `tinyqoi::Qoi` or an
+/// [`ImageRaw`](embedded_graphics::image::ImageRaw) baked into flash. The
+/// image is centred in the widget's bounds, which default to the image size.
+pub struct Image<'a, M, I>
+where
+    M: Copy + Clone,
+{
+    ph: PhantomData<M>,
+    image: &'a I,
+    pos: Point,
+    size: Option<Size>,
+    state_manager: StateManager,
+}
+
+// Manual impl: cloning only copies the reference, so `I` needn't be `Clone`
+impl<M, I> Clone for Image<'_, M, I>
+where
+    M: Copy + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            ph: PhantomData,
+            image: self.image,
+            pos: self.pos,
+            size: self.size,
+            state_manager: self.state_manager,
+        }
+    }
+}
+
+impl<'a, M, I> Image<'a, M, I>
+where
+    M: Copy + Clone,
+    I: ImageDrawable,
+{
+    pub fn new(image: &'a I, pos: Point) -> Self {
+        Self {
+            ph: PhantomData,
+            image,
+            pos,
+            size: None,
+            state_manager: StateManager::read_only(),
+        }
+    }
+    /// Reserve a larger area than the image itself, e.g. to line up with
+    /// other widgets in a layout
+    pub fn with_size(mut self, size: Size) -> Self {
+        self.size = Some(size);
+        self
+    }
+    pub fn set(&mut self, image: &'a I) {
+        self.image = image;
+        self.state_manager.mark_dirty();
+    }
+}
+
+impl<M, I> Widget<M> for Image<'_, M, I>
+where
+    M: Copy + Clone,
+    I: ImageDrawable,
+{
+    fn to_message(&self) -> Option<M> {
+        None
+    }
+    fn get_state_manager(&self) -> &StateManager {
+        &self.state_manager
+    }
+    fn get_state_manager_mut(&mut self) -> &mut StateManager {
+        &mut self.state_manager
+    }
+}
+
+impl<D, T, C, M, I> ThemedWidget<D, T, C> for Image<'_, M, I>
+where
+    C: PixelColor + Default + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    M: Copy,
+    I: ImageDrawable<Color = C>,
+{
+    fn draw_with_theme(&self, target: &mut D, _theme: &T) -> Result<(), <D as DrawTarget>::Error> {
+        let image_size = self.image.bounding_box().size;
+        let offset = self.bounding_box().size.saturating_sub(image_size) / 2;
+        embedded_graphics::image::Image::new(self.image, self.pos + offset).draw(target)?;
+        Ok(())
+    }
+}
+
+impl<M, D, T, C, I> Element<M, D, T, C> for Image<'_, M, I>
+where
+    M: Copy + Clone,
+    D: DrawTarget<Color = C>,
+    C: PixelColor + Default + From<Rgb888>,
+    T: Theme<C>,
+    I: ImageDrawable<Color = C>,
+{
+}
+
+impl<M, I> Transform for Image<'_, M, I>
+where
+    M: Copy + Clone,
+    I: ImageDrawable,
+{
+    fn translate(&self, by: Point) -> Self {
+        let mut new_image = self.clone();
+        new_image.pos += by;
+        new_image
+    }
+
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.pos += by;
+        self
+    }
+}
+impl<M, I> Place for Image<'_, M, I>
+where
+    M: Copy + Clone,
+    I: ImageDrawable,
+{
+    fn place(&mut self, bounds: Rectangle) {
+        self.pos = bounds.top_left;
+        self.size = Some(bounds.size);
+        self.state_manager.mark_dirty();
+    }
+}
+
+impl<M, I> Dimensions for Image<'_, M, I>
+where
+    M: Copy + Clone,
+    I: ImageDrawable,
+{
+    fn bounding_box(&self) -> Rectangle {
+        let size = self.size.unwrap_or_else(|| self.image.bounding_box().size);
+        Rectangle::new(self.pos, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{image::ImageRaw, mock_display::MockDisplay};
+
+    use super::*;
+    use crate::themes::DefaultTheme;
+
+    #[derive(Clone, Copy)]
+    enum Message {}
+
+    #[test]
+    fn image_is_centred_in_its_bounds() {
+        let red: ImageRaw<Rgb888> = ImageRaw::new(&[255, 0, 0, 255, 0, 0, 255, 0, 0, 255, 0, 0], 2);
+        let image: Image<Message, _> =
+            Image::new(&red, Point::new(1, 1)).with_size(Size::new(6, 6));
+        assert_eq!(
+            image.bounding_box(),
+            Rectangle::new(Point::new(1, 1), Size::new(6, 6))
+        );
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        image
+            .draw_with_theme(&mut display, &DefaultTheme::new())
+            .unwrap();
+        display.assert_pattern(&[
+            "     ", //
+            "     ", //
+            "     ", //
+            "   RR", //
+            "   RR", //
+        ]);
+    }
+}
@@ -0,0 +1,661 @@
+//! An on-screen keyboard for devices without a physical one.
+//!
+//! The keys come from [`KeyboardLayout`] tables. The built-in [`QWERTY`],
+//! [`SYMBOLS`] and [`NUMERIC`] layouts are consts like any other, so a
+//! layout for another language is one more table:
+//!
+//! ```
+//! use embui::widgets::keyboard::{KeyCap::*, KeyboardLayout};
+//!
+//! const QWERTZ: KeyboardLayout = KeyboardLayout {
+//!     rows: &[
+//!         &[
+//!             Char('q', 'Q'), Char('w', 'W'), Char('e', 'E'), Char('r', 'R'),
+//!             Char('t', 'T'), Char('z', 'Z'), Char('u', 'U'), Char('i', 'I'),
+//!             Char('o', 'O'), Char('p', 'P'),
+//!         ],
+//!         // ...
+//!         &[Layout(1, "?123"), Space, Enter],
+//!     ],
+//! };
+//! ```
+use embedded_graphics::{
+    mono_font::MonoTextStyle, pixelcolor::Rgb888, prelude::*, primitives::Rectangle,
+};
+use embedded_text::{
+    TextBox,
+    alignment::{HorizontalAlignment, VerticalAlignment},
+    style::{HeightMode, TextBoxStyleBuilder, VerticalOverdraw},
+};
+
+use crate::{
+    InputEvent, Key, Response, StateFlags, StateManager, ThemedWidget, Widget,
+    layout::{Place, split},
+    screen::Element,
+    themes::{Style, Theme},
+    widgets::{frame::Frame, progress::Orientation},
+};
+
+/// One key of a [`KeyboardLayout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCap {
+    /// Types the first character, or the second one with shift or caps lock
+    Char(char, char),
+    /// Types a space
+    Space,
+    /// Shifts the next character, caps lock when tapped again
+    Shift,
+    Backspace,
+    Enter,
+    /// Switches to the layout at this index of
+    /// [`OnScreenKeyboard::with_layouts`], showing this label
+    Layout(usize, &'static str),
+}
+
+impl KeyCap {
+    /// Width in half keys: a character is 2, space is 10
+    pub const fn width(&self) -> u32 {
+        match self {
+            KeyCap::Char(..) => 2,
+            KeyCap::Space => 10,
+            KeyCap::Shift | KeyCap::Backspace | KeyCap::Enter | KeyCap::Layout(..) => 3,
+        }
+    }
+}
+
+/// Rows of keys, top to bottom.
+///
+/// The widest row fills the keyboard, narrower rows are centred with keys
+/// of the same size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyboardLayout {
+    pub rows: &'static [&'static [KeyCap]],
+}
+
+impl KeyboardLayout {
+    /// Width of the widest row in half keys
+    pub const fn width(&self) -> u32 {
+        let mut widest = 0;
+        let mut row = 0;
+        while row < self.rows.len() {
+            let mut width = 0;
+            let mut key = 0;
+            while key < self.rows[row].len() {
+                width += self.rows[row][key].width();
+                key += 1;
+            }
+            if width > widest {
+                widest = width;
+            }
+            row += 1;
+        }
+        widest
+    }
+}
+
+/// Letters, with a key to [`SYMBOLS`] as the second layout
+pub const QWERTY: KeyboardLayout = {
+    use KeyCap::*;
+    KeyboardLayout {
+        rows: &[
+            &[
+                Char('q', 'Q'),
+                Char('w', 'W'),
+                Char('e', 'E'),
+                Char('r', 'R'),
+                Char('t', 'T'),
+                Char('y', 'Y'),
+                Char('u', 'U'),
+                Char('i', 'I'),
+                Char('o', 'O'),
+                Char('p', 'P'),
+            ],
+            &[
+                Char('a', 'A'),
+                Char('s', 'S'),
+                Char('d', 'D'),
+                Char('f', 'F'),
+                Char('g', 'G'),
+                Char('h', 'H'),
+                Char('j', 'J'),
+                Char('k', 'K'),
+                Char('l', 'L'),
+            ],
+            &[
+                Shift,
+                Char('z', 'Z'),
+                Char('x', 'X'),
+                Char('c', 'C'),
+                Char('v', 'V'),
+                Char('b', 'B'),
+                Char('n', 'N'),
+                Char('m', 'M'),
+                Backspace,
+            ],
+            &[
+                Layout(1, "?123"),
+                Char(',', ','),
+                Space,
+                Char('.', '.'),
+                Enter,
+            ],
+        ],
+    }
+};
+
+/// Digits and punctuation, with a key back to [`QWERTY`] as the first
+/// layout
+pub const SYMBOLS: KeyboardLayout = {
+    use KeyCap::*;
+    KeyboardLayout {
+        rows: &[
+            &[
+                Char('1', '1'),
+                Char('2', '2'),
+                Char('3', '3'),
+                Char('4', '4'),
+                Char('5', '5'),
+                Char('6', '6'),
+                Char('7', '7'),
+                Char('8', '8'),
+                Char('9', '9'),
+                Char('0', '0'),
+            ],
+            &[
+                Char('@', '@'),
+                Char('#', '#'),
+                Char('$', '$'),
+                Char('%', '%'),
+                Char('&', '&'),
+                Char('*', '*'),
+                Char('-', '-'),
+                Char('+', '+'),
+                Char('(', '('),
+                Char(')', ')'),
+            ],
+            &[
+                Char('_', '_'),
+                Char('/', '/'),
+                Char(':', ':'),
+                Char(';', ';'),
+                Char('!', '!'),
+                Char('?', '?'),
+                Char('\'', '\''),
+                Char('"', '"'),
+                Backspace,
+            ],
+            &[
+                Layout(0, "ABC"),
+                Char(',', ','),
+                Space,
+                Char('.', '.'),
+                Enter,
+            ],
+        ],
+    }
+};
+
+/// A keypad for numbers and PINs, on its own
+pub const NUMERIC: KeyboardLayout = {
+    use KeyCap::*;
+    KeyboardLayout {
+        rows: &[
+            &[Char('1', '1'), Char('2', '2'), Char('3', '3')],
+            &[Char('4', '4'), Char('5', '5'), Char('6', '6')],
+            &[Char('7', '7'), Char('8', '8'), Char('9', '9')],
+            &[Backspace, Char('0', '0'), Enter],
+        ],
+    }
+};
+
+/// [`QWERTY`] and [`SYMBOLS`]
+pub const DEFAULT_LAYOUTS: &[KeyboardLayout] = &[QWERTY, SYMBOLS];
+
+/// Whether letters come out in upper case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Shift {
+    #[default]
+    Off,
+    /// For the next character only
+    Once,
+    /// Caps lock, until shift is tapped again
+    Locked,
+}
+
+/// A keyboard drawn on a touch screen.
+///
+/// Every key typed sends the message made by [`OnScreenKeyboard::on_key`]
+/// from the key's [`InputEvent`]: [`InputEvent::KeyPress`] for characters
+/// and space, [`Key::Backspace`] and [`Key::Enter`] for the others. The
+/// keyboard doesn't take the focus, so the application passes the event
+/// on to the focused widget with [`Command::Input`](crate::Command::Input):
+///
+/// ```ignore
+/// fn update(&mut self, message: Message) -> Command<Message> {
+///     match message {
+///         Message::Typed(event) => return Command::Input(event),
+///         // ...
+///     }
+///     Command::None
+/// }
+/// ```
+///
+/// Keys type when released, so a finger can slide to the right key first.
+#[derive(Clone, Debug)]
+pub struct OnScreenKeyboard<'a, M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    layouts: &'a [KeyboardLayout],
+    layout: usize,
+    shift: Shift,
+    pressed: Option<(usize, usize)>,
+    on_key: Option<fn(InputEvent) -> M>,
+    bounds: Rectangle,
+    style: Style<C>,
+    state_manager: StateManager,
+}
+
+impl<'a, M, C> OnScreenKeyboard<'a, M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// A keyboard as wide as a screen of `screen` size, along its bottom
+    /// edge and two fifths as high, with the [`DEFAULT_LAYOUTS`]
+    pub fn new(screen: Size) -> Self {
+        let height = screen.height * 2 / 5;
+        Self {
+            layouts: DEFAULT_LAYOUTS,
+            layout: 0,
+            shift: Shift::Off,
+            pressed: None,
+            on_key: None,
+            bounds: Rectangle::new(
+                Point::new(0, (screen.height - height) as i32),
+                Size::new(screen.width, height),
+            ),
+            style: Style::new(),
+            state_manager: StateManager::new(),
+        }
+    }
+
+    /// Send a message for every key typed
+    pub fn on_key(mut self, message: fn(InputEvent) -> M) -> Self {
+        self.on_key = Some(message);
+        self
+    }
+    /// Use these layouts instead, starting with the first one. An empty
+    /// slice is ignored.
+    pub fn with_layouts(mut self, layouts: &'a [KeyboardLayout]) -> Self {
+        if !layouts.is_empty() {
+            self.layouts = layouts;
+            self.layout = 0;
+            self.pressed = None;
+        }
+        self
+    }
+    /// Change the height, keeping the bottom edge in place
+    pub fn with_height(mut self, height: u32) -> Self {
+        let bottom = self.bounds.top_left.y + self.bounds.size.height as i32;
+        self.bounds = Rectangle::new(
+            Point::new(self.bounds.top_left.x, bottom - height as i32),
+            Size::new(self.bounds.size.width, height),
+        );
+        self
+    }
+    /// Override theme colours or font of the keys
+    pub fn with_style(mut self, style: Style<C>) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Index of the shown layout
+    pub fn layout(&self) -> usize {
+        self.layout
+    }
+    /// Show another layout
+    pub fn set_layout(&mut self, index: usize) {
+        if index < self.layouts.len() && index != self.layout {
+            self.layout = index;
+            self.pressed = None;
+            self.state_manager.mark_dirty();
+        }
+    }
+    pub fn shift(&self) -> Shift {
+        self.shift
+    }
+    pub fn set_shift(&mut self, shift: Shift) {
+        if shift != self.shift {
+            self.shift = shift;
+            self.state_manager.mark_dirty();
+        }
+    }
+
+    fn rows(&self) -> &'static [&'static [KeyCap]] {
+        self.layouts[self.layout].rows
+    }
+    /// The area of key `index` in `row` of the shown layout
+    pub fn key_bounds(&self, row: usize, index: usize) -> Rectangle {
+        let rows = self.rows();
+        let line = split(self.bounds, Orientation::Vertical, rows.len(), row, 0);
+        let keys = rows[row];
+        let total = self.layouts[self.layout].width().max(1) as i32;
+        let width = self.bounds.size.width as i32;
+        let used: u32 = keys.iter().map(KeyCap::width).sum();
+        let mut offset = (total - used as i32) / 2;
+        for key in &keys[..index] {
+            offset += key.width() as i32;
+        }
+        let left = offset * width / total;
+        let right = (offset + keys[index].width() as i32) * width / total;
+        Rectangle::new(
+            Point::new(line.top_left.x + left, line.top_left.y),
+            Size::new((right - left) as u32, line.size.height),
+        )
+    }
+    fn key_at(&self, point: Point) -> Option<(usize, usize)> {
+        let rows = self.rows();
+        (0..rows.len())
+            .flat_map(|row| (0..rows[row].len()).map(move |index| (row, index)))
+            .find(|&(row, index)| self.key_bounds(row, index).contains(point))
+    }
+    fn press(&mut self, key: Option<(usize, usize)>) {
+        if key != self.pressed {
+            self.pressed = key;
+            self.state_manager.mark_dirty();
+        }
+    }
+
+    /// Act on a released key, returning the event it types
+    fn type_key(&mut self, cap: KeyCap) -> Option<InputEvent> {
+        match cap {
+            KeyCap::Char(lower, upper) => {
+                let upper_case = self.shift != Shift::Off;
+                if self.shift == Shift::Once {
+                    self.set_shift(Shift::Off);
+                }
+                Some(InputEvent::KeyPress(if upper_case { upper } else { lower }))
+            }
+            KeyCap::Space => Some(InputEvent::KeyPress(' ')),
+            KeyCap::Backspace => Some(InputEvent::Key(Key::Backspace)),
+            KeyCap::Enter => Some(InputEvent::Key(Key::Enter)),
+            KeyCap::Shift => {
+                self.set_shift(match self.shift {
+                    Shift::Off => Shift::Once,
+                    Shift::Once => Shift::Locked,
+                    Shift::Locked => Shift::Off,
+                });
+                None
+            }
+            KeyCap::Layout(index, _) => {
+                self.set_layout(index);
+                None
+            }
+        }
+    }
+
+    fn label<'b>(&self, cap: KeyCap, buffer: &'b mut [u8; 4]) -> &'b str {
+        match cap {
+            KeyCap::Char(lower, upper) => {
+                let c = if self.shift == Shift::Off {
+                    lower
+                } else {
+                    upper
+                };
+                c.encode_utf8(buffer)
+            }
+            KeyCap::Space => "",
+            KeyCap::Shift if self.shift == Shift::Locked => "Caps",
+            KeyCap::Shift => "Shift",
+            KeyCap::Backspace => "Del",
+            KeyCap::Enter => "Enter",
+            KeyCap::Layout(_, label) => label,
+        }
+    }
+}
+
+impl<M, C> Widget<M> for OnScreenKeyboard<'_, M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// Nothing, every key sends its own message
+    fn to_message(&self) -> Option<M> {
+        None
+    }
+    fn handle_event(&mut self, event: InputEvent) -> Response<M> {
+        let state = &mut self.state_manager;
+        if !state.is_enabled() || !state.is_visible() {
+            return Response::NotChanged;
+        }
+        let inside = match event {
+            InputEvent::Touch(point)
+            | InputEvent::TouchMove(point)
+            | InputEvent::TouchRelease(point)
+            | InputEvent::MouseMove(point)
+            | InputEvent::MouseEnter(point)
+            | InputEvent::MouseLeave(point)
+            | InputEvent::MouseDown(point)
+            | InputEvent::MouseUp(point) => self.bounds.contains(point),
+            InputEvent::KeyPress(_) | InputEvent::Key(_) => false,
+        };
+        let was_pressed = state.is_pressed();
+        let state_changed = state.handle_event(event, inside);
+
+        match event {
+            InputEvent::Touch(point) | InputEvent::MouseDown(point) if inside => {
+                self.press(self.key_at(point));
+            }
+            InputEvent::TouchMove(point) | InputEvent::MouseMove(point) if was_pressed => {
+                self.press(self.key_at(point));
+            }
+            InputEvent::TouchRelease(point) | InputEvent::MouseUp(point) if was_pressed => {
+                let key = self.pressed.filter(|&key| self.key_at(point) == Some(key));
+                self.press(None);
+                if let Some((row, index)) = key {
+                    let typed = self.type_key(self.rows()[row][index]);
+                    let message = typed.and_then(|event| self.on_key.map(|on_key| on_key(event)));
+                    return Response::Consumed(message);
+                }
+            }
+            _ => {}
+        }
+        if was_pressed || self.state_manager.is_pressed() {
+            Response::Consumed(None)
+        } else if state_changed {
+            Response::Changed(None)
+        } else {
+            Response::NotChanged
+        }
+    }
+    fn get_state_manager(&self) -> &StateManager {
+        &self.state_manager
+    }
+    fn get_state_manager_mut(&mut self) -> &mut StateManager {
+        &mut self.state_manager
+    }
+}
+
+impl<D, T, C, M> ThemedWidget<D, T, C> for OnScreenKeyboard<'_, M, C>
+where
+    C: PixelColor + Default + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    M: Copy,
+{
+    fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), <D as DrawTarget>::Error> {
+        Frame::new(self.bounds)
+            .with_fill(theme.panel_background_color())
+            .draw(target)?;
+
+        let textbox_style = TextBoxStyleBuilder::new()
+            .height_mode(HeightMode::Exact(VerticalOverdraw::Hidden))
+            .alignment(HorizontalAlignment::Center)
+            .vertical_alignment(VerticalAlignment::Middle)
+            .build();
+        for (row, keys) in self.rows().iter().enumerate() {
+            for (index, &cap) in keys.iter().enumerate() {
+                let mut flags = StateFlags::NONE;
+                flags.set(StateFlags::DISABLED, !self.is_enabled());
+                flags.set(StateFlags::PRESSED, self.pressed == Some((row, index)));
+                flags.set(
+                    StateFlags::SELECTED,
+                    cap == KeyCap::Shift && self.shift != Shift::Off,
+                );
+                let style = self.style.resolve(theme, flags);
+                let bounds = self.key_bounds(row, index);
+                Frame::themed(
+                    bounds.offset(-(theme.spacing_xs() as i32)),
+                    theme,
+                    flags,
+                    style.background,
+                    style.border,
+                )
+                .draw(target)?;
+                let mut buffer = [0; 4];
+                TextBox::with_textbox_style(
+                    self.label(cap, &mut buffer),
+                    bounds.offset(-(style.padding as i32)),
+                    MonoTextStyle::new(style.font, style.text),
+                    textbox_style,
+                )
+                .draw(target)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<M, D, T, C> Element<M, D, T, C> for OnScreenKeyboard<'_, M, C>
+where
+    M: Copy + Clone,
+    D: DrawTarget<Color = C>,
+    C: PixelColor + Default + From<Rgb888>,
+    T: Theme<C>,
+{
+}
+
+impl<M, C> Transform for OnScreenKeyboard<'_, M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn translate(&self, by: Point) -> Self {
+        let mut moved = self.clone();
+        moved.translate_mut(by);
+        moved
+    }
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.bounds.top_left += by;
+        self
+    }
+}
+
+impl<M, C> Place for OnScreenKeyboard<'_, M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn place(&mut self, bounds: Rectangle) {
+        self.bounds = bounds;
+        self.state_manager.mark_dirty();
+    }
+}
+
+impl<M, C> Dimensions for OnScreenKeyboard<'_, M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Message {
+        Typed(InputEvent),
+    }
+
+    fn keyboard() -> OnScreenKeyboard<'static, Message, Rgb888> {
+        OnScreenKeyboard::new(Size::new(240, 320)).on_key(Message::Typed)
+    }
+
+    fn tap(
+        keyboard: &mut OnScreenKeyboard<'static, Message, Rgb888>,
+        row: usize,
+        index: usize,
+    ) -> Option<InputEvent> {
+        let point = keyboard.key_bounds(row, index).center();
+        keyboard.handle_event(InputEvent::Touch(point));
+        let Message::Typed(event) = keyboard
+            .handle_event(InputEvent::TouchRelease(point))
+            .message()?;
+        Some(event)
+    }
+
+    #[test]
+    fn fills_the_bottom_of_the_screen() {
+        let keyboard = keyboard();
+        assert_eq!(
+            keyboard.bounding_box(),
+            Rectangle::new(Point::new(0, 192), Size::new(240, 128))
+        );
+        // The widest rows span the full width, the home row is centred
+        assert_eq!(keyboard.key_bounds(0, 0).top_left, Point::new(0, 192));
+        assert_eq!(keyboard.key_bounds(0, 9).size, Size::new(24, 32));
+        assert_eq!(keyboard.key_bounds(1, 0).top_left.x, 12);
+        let backspace = keyboard.key_bounds(2, 8);
+        assert_eq!(backspace.top_left.x + backspace.size.width as i32, 240);
+    }
+
+    #[test]
+    fn shift_once_then_caps_lock() {
+        let mut keyboard = keyboard();
+        assert_eq!(tap(&mut keyboard, 0, 0), Some(InputEvent::KeyPress('q')));
+
+        assert_eq!(tap(&mut keyboard, 2, 0), None);
+        assert_eq!(tap(&mut keyboard, 0, 1), Some(InputEvent::KeyPress('W')));
+        assert_eq!(tap(&mut keyboard, 0, 1), Some(InputEvent::KeyPress('w')));
+
+        tap(&mut keyboard, 2, 0);
+        tap(&mut keyboard, 2, 0);
+        assert_eq!(keyboard.shift(), Shift::Locked);
+        assert_eq!(tap(&mut keyboard, 0, 2), Some(InputEvent::KeyPress('E')));
+        assert_eq!(tap(&mut keyboard, 0, 2), Some(InputEvent::KeyPress('E')));
+        assert_eq!(
+            tap(&mut keyboard, 2, 8),
+            Some(InputEvent::Key(Key::Backspace))
+        );
+    }
+
+    #[test]
+    fn layout_keys_switch_pages() {
+        let mut keyboard = keyboard();
+        assert_eq!(tap(&mut keyboard, 3, 0), None);
+        assert_eq!(keyboard.layout(), 1);
+        assert_eq!(tap(&mut keyboard, 0, 0), Some(InputEvent::KeyPress('1')));
+        tap(&mut keyboard, 3, 0);
+        assert_eq!(tap(&mut keyboard, 3, 2), Some(InputEvent::KeyPress(' ')));
+
+        // A finger sliding onto another key types that one
+        let from = keyboard.key_bounds(0, 0).center();
+        let to = keyboard.key_bounds(0, 1).center();
+        keyboard.handle_event(InputEvent::Touch(from));
+        keyboard.handle_event(InputEvent::TouchMove(to));
+        let response = keyboard.handle_event(InputEvent::TouchRelease(to));
+        assert_eq!(
+            response.message(),
+            Some(Message::Typed(InputEvent::KeyPress('w')))
+        );
+
+        let mut pin = keyboard.with_layouts(&[NUMERIC]);
+        assert_eq!(tap(&mut pin, 3, 1), Some(InputEvent::KeyPress('0')));
+    }
+}
@@ -0,0 +1,241 @@
+use core::marker::PhantomData;
+
+use embedded_graphics::{
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle, StyledDrawable},
+};
+
+use crate::{
+    StateManager, Theme, ThemedWidget, Widget,
+    widgets::{chart::Thresholds, progress::Orientation},
+};
+
+/// A segmented bar meter, like the VU meter of an audio mixer.
+///
+/// The highest recent level is held as a single lit peak segment for a number
+/// of [`tick`](Self::tick)s before it falls back to the current level.
+#[derive(Clone, Widget)]
+pub struct LevelMeter<M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    ph: PhantomData<(M, C)>,
+    value: i32,
+    min: i32,
+    max: i32,
+    segments: u32,
+    orientation: Orientation,
+    thresholds: Option<Thresholds>,
+    peak: i32,
+    peak_hold: u32,
+    hold_left: u32,
+    #[position]
+    pos: Point,
+    #[size]
+    size: Size,
+    #[state]
+    state_manager: StateManager,
+}
+
+impl<M, C> LevelMeter<M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// A vertical meter with 10 segments going from 0 to 100
+    pub fn new(pos: Point, size: Size) -> Self {
+        Self {
+            ph: PhantomData,
+            value: 0,
+            min: 0,
+            max: 100,
+            segments: 10,
+            orientation: Orientation::Vertical,
+            thresholds: None,
+            peak: 0,
+            peak_hold: 0,
+            hold_left: 0,
+            pos,
+            size,
+            state_manager: StateManager::read_only(),
+        }
+    }
+
+    /// The values of an empty and a full meter, in either order
+    pub fn with_range(mut self, min: i32, max: i32) -> Self {
+        self.min = min.min(max);
+        self.max = min.max(max);
+        self.value = self.min;
+        self.peak = self.min;
+        self
+    }
+    pub fn with_segments(mut self, segments: u32) -> Self {
+        self.segments = segments.max(1);
+        self
+    }
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+    /// Colour segments by the level they stand for
+    pub fn with_thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.thresholds = Some(thresholds);
+        self
+    }
+    /// Hold the peak for `ticks` calls to [`tick`](Self::tick), 0 disables
+    /// the peak indicator
+    pub fn with_peak_hold(mut self, ticks: u32) -> Self {
+        self.peak_hold = ticks;
+        self
+    }
+
+    pub fn set(&mut self, value: i32) {
+        if value != self.value {
+            self.state_manager.mark_dirty();
+        }
+        self.value = value;
+        if value >= self.peak {
+            self.peak = value;
+            self.hold_left = self.peak_hold;
+        }
+    }
+    pub fn get(&self) -> i32 {
+        self.value
+    }
+    pub fn peak(&self) -> i32 {
+        self.peak
+    }
+    /// Age the held peak by one frame
+    pub fn tick(&mut self) {
+        if self.hold_left > 0 {
+            self.hold_left -= 1;
+        } else if self.peak != self.value {
+            self.peak = self.value;
+            self.state_manager.mark_dirty();
+        }
+    }
+
+    /// Number of segments lit for `value`
+    fn lit(&self, value: i32) -> u32 {
+        let span = (self.max as i64 - self.min as i64).max(1);
+        let offset = (value as i64).clamp(self.min as i64, self.max as i64) - self.min as i64;
+        ((offset * self.segments as i64 + span - 1) / span) as u32
+    }
+
+    /// Bounds of segment `index`, counted from the empty end of the meter
+    fn segment(&self, index: u32) -> Rectangle {
+        let bounds = self.bounding_box();
+        let (length, width) = match self.orientation {
+            Orientation::Horizontal => (bounds.size.width, bounds.size.height),
+            Orientation::Vertical => (bounds.size.height, bounds.size.width),
+        };
+        let segments = self.segments.max(1);
+        let start = index * length / segments;
+        let end = (index + 1) * length / segments;
+        // One pixel gap between segments
+        let size = (end - start).saturating_sub(1).max(1);
+        match self.orientation {
+            Orientation::Horizontal => Rectangle::new(
+                bounds.top_left + Point::new(start as i32, 0),
+                Size::new(size, width),
+            ),
+            Orientation::Vertical => Rectangle::new(
+                // A meter without height still has one-pixel segments
+                bounds.top_left + Point::new(0, length.saturating_sub(start + size) as i32),
+                Size::new(width, size),
+            ),
+        }
+    }
+}
+
+impl<D, T, C, M> ThemedWidget<D, T, C> for LevelMeter<M, C>
+where
+    C: PixelColor + Default + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    M: Copy,
+{
+    fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), <D as DrawTarget>::Error> {
+        let lit = self.lit(self.value);
+        let peak = if self.peak_hold > 0 {
+            self.lit(self.peak)
+        } else {
+            0
+        };
+        let span = self.max as i64 - self.min as i64;
+        for index in 0..self.segments {
+            let color = if index < lit || index + 1 == peak {
+                match self.thresholds {
+                    // The level at the top edge of the segment decides its colour
+                    Some(thresholds) => {
+                        let level =
+                            self.min as i64 + (index as i64 + 1) * span / self.segments as i64;
+                        thresholds.color(theme, level as i32)
+                    }
+                    None if index >= lit => theme.meter_peak_color(),
+                    None => theme.meter_fill_color(),
+                }
+            } else {
+                theme.meter_track_color()
+            };
+            self.segment(index)
+                .draw_styled(&PrimitiveStyle::with_fill(color), target)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy)]
+    enum Message {}
+
+    #[test]
+    fn peak_is_held_then_falls() {
+        let mut meter: LevelMeter<Message, Rgb888> =
+            LevelMeter::new(Point::zero(), Size::new(8, 40)).with_peak_hold(2);
+        meter.set(80);
+        meter.set(30);
+        assert_eq!(meter.peak(), 80);
+
+        meter.tick();
+        meter.tick();
+        assert_eq!(meter.peak(), 80);
+        meter.tick();
+        assert_eq!(meter.peak(), 30);
+    }
+
+    #[test]
+    fn reversed_range_is_swapped() {
+        let meter: LevelMeter<Message, Rgb888> = LevelMeter::new(Point::zero(), Size::new(8, 40))
+            .with_segments(4)
+            .with_range(0, -40);
+        assert_eq!(meter.get(), -40);
+        assert_eq!(meter.lit(-20), 2);
+        assert_eq!(meter.lit(0), 4);
+    }
+
+    #[test]
+    fn segments_fill_from_bottom() {
+        let meter: LevelMeter<Message, Rgb888> =
+            LevelMeter::new(Point::zero(), Size::new(8, 40)).with_segments(4);
+        assert_eq!(meter.lit(0), 0);
+        assert_eq!(meter.lit(1), 1);
+        assert_eq!(meter.lit(100), 4);
+        assert_eq!(
+            meter.segment(0),
+            Rectangle::new(Point::new(0, 31), Size::new(8, 9))
+        );
+        assert_eq!(
+            meter.segment(3),
+            Rectangle::new(Point::new(0, 1), Size::new(8, 9))
+        );
+
+        let flat: LevelMeter<Message, Rgb888> = LevelMeter::new(Point::zero(), Size::new(8, 0));
+        assert_eq!(flat.segment(9).top_left, Point::zero());
+    }
+}
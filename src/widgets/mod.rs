@@ -1,7 +1,30 @@
-pub use button::Button;
+pub use button::{Button, IconPlacement};
+pub use chart::{BarChart, Grid, LineChart, ScrollMode, Sparkline, Thresholds, YRange};
+pub use dropdown::Dropdown;
+pub use frame::{Bevel, Fill, Frame};
+pub use gauge::Gauge;
+pub use icon::{Icon, IconView};
+pub use image::Image;
+pub use keyboard::OnScreenKeyboard;
+pub use level_meter::LevelMeter;
 pub use number::Number;
+pub use popup::PopupMenu;
+pub use progress::{Orientation, ProgressBar};
 pub use state::StateManager;
-pub use state::WidgetState;
+pub use state::{StateFlags, WidgetId, WidgetState};
+pub use tabs::Tabs;
 pub mod button;
+pub mod chart;
+mod dropdown;
+mod frame;
+mod gauge;
+mod icon;
+pub mod icons;
+mod image;
+pub mod keyboard;
+mod level_meter;
 mod number;
+mod popup;
+mod progress;
 pub mod state;
+mod tabs;
@@ -1,74 +1,67 @@
 use core::{fmt::Write, marker::PhantomData};
 
-use embedded_graphics::{
-    mono_font::MonoTextStyle,
-    pixelcolor::Rgb888,
-    prelude::*,
-    primitives::{PrimitiveStyleBuilder, Rectangle, StyledDrawable},
-};
+use embedded_graphics::{mono_font::MonoTextStyle, pixelcolor::Rgb888, prelude::*};
 use embedded_text::{
     TextBox,
-    alignment::{HorizontalAlignment, VerticalAlignment},
+    alignment::VerticalAlignment,
     style::{HeightMode, TextBoxStyleBuilder, VerticalOverdraw},
 };
 
-use crate::{StateManager, Theme, ThemedWidget, Widget, WidgetState, screen::Element};
-#[derive(Clone)]
-pub struct Number<M>
+use crate::{
+    StateManager, Theme, ThemedWidget, Widget,
+    binding::{Binding, Bound},
+    themes::Style,
+    widgets::frame::Frame,
+};
+#[derive(Clone, Widget)]
+pub struct Number<'a, M, C = Rgb888>
 where
     M: Copy + Clone,
+    C: PixelColor,
 {
     ph: PhantomData<M>,
-    number: i32,
+    #[binding]
+    number: Bound<'a, i32>,
+    style: Style<C>,
+    #[state]
     state_manager: StateManager,
+    #[position]
     pos: Point,
+    #[size]
     size: Size,
 }
 
-impl<M: Copy> Number<M> {
+impl<'a, M: Copy, C: PixelColor> Number<'a, M, C> {
     pub fn new(pos: Point, size: Size) -> Self {
         Self {
             ph: PhantomData,
-            number: 0,
+            number: Bound::new(0),
+            style: Style::new(),
             state_manager: StateManager::new(),
             pos,
             size,
         }
     }
-    pub fn set(&mut self, number: i32) {
-        self.number = number;
-    }
-    pub fn get(&self) -> i32 {
-        self.number
-    }
-}
-impl<M: Copy + Clone> Transform for Number<M> {
-    fn translate(&self, by: Point) -> Self {
-        let mut new_number = self.clone();
-        new_number.pos += by;
-        new_number
-    }
-
-    fn translate_mut(&mut self, by: Point) -> &mut Self {
-        self.pos += by;
+    /// Override theme colours, font, padding or alignment for this number
+    pub fn with_style(mut self, style: Style<C>) -> Self {
+        self.style = style;
         self
     }
-}
-impl<M: Copy> Widget<M> for Number<M> {
-    fn to_message(&self) -> Option<M> {
-        None
+    /// Show a value owned by the model instead of the widget
+    pub fn bind(mut self, binding: impl Into<Binding<'a, i32>>) -> Self {
+        self.number.bind(binding.into());
+        self
     }
-
-    fn get_state_manager(&self) -> &super::StateManager {
-        &self.state_manager
+    /// Set the value, writing through to a bound [`Signal`](crate::Signal)
+    pub fn set(&mut self, number: i32) {
+        self.number.set(number);
     }
-
-    fn get_state_manager_mut(&mut self) -> &mut super::StateManager {
-        &mut self.state_manager
+    pub fn get(&self) -> i32 {
+        self.number.get()
     }
 }
 
-impl<D, T, C, M> ThemedWidget<D, T, C> for Number<M>
+impl<D, T, C, M> ThemedWidget<D, T, C> for Number<'_, M, C>
 where
     C: PixelColor + Default + From<Rgb888>,
     D: DrawTarget<Color = C>,
@@ -76,78 +69,35 @@ where
     M: Copy,
 {
     fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), <D as DrawTarget>::Error> {
-        let (background_color, text_color, border_color) = match Widget::get_state(self) {
-            WidgetState::Normal => (
-                theme.button_normal_bg(),
-                theme.button_normal_text(),
-                theme.button_normal_border(),
-            ),
-            WidgetState::Hovered => (
-                theme.button_hovered_bg(),
-                theme.button_hovered_text(),
-                theme.button_hovered_border(),
-            ),
-            WidgetState::Pressed => (
-                theme.button_pressed_bg(),
-                theme.button_pressed_text(),
-                theme.button_pressed_border(),
-            ),
-            WidgetState::Focused => (
-                theme.button_hovered_bg(),
-                theme.button_hovered_text(),
-                theme.primary_color(), // Different border for focus
-            ),
-            WidgetState::Disabled => (
-                theme.button_disabled_bg(),
-                theme.button_disabled_text(),
-                theme.button_disabled_border(),
-            ),
-        };
+        let flags = self.state_flags();
+        let style = self.style.resolve(theme, flags);
         let textbox_style = TextBoxStyleBuilder::new()
             .height_mode(HeightMode::Exact(VerticalOverdraw::Hidden))
-            .alignment(HorizontalAlignment::Center)
+            .alignment(style.alignment)
             .vertical_alignment(VerticalAlignment::Middle)
             .paragraph_spacing(6)
             .build();
-        let character_style = MonoTextStyle::new(theme.normal_font(), text_color);
+        let character_style = MonoTextStyle::new(style.font, style.text);
 
-        let mut text: heapless::String<8> = heapless::String::new();
+        let mut text: heapless::String<11> = heapless::String::new();
 
-        write!(&mut text, "{}", self.number).unwrap();
+        write!(&mut text, "{}", self.number.get()).unwrap();
         let label = TextBox::with_textbox_style(
             text.as_str(),
-            self.bounding_box(),
+            self.bounding_box().offset(-(style.padding as i32)),
             character_style,
             textbox_style,
         );
 
-        let outline_style = PrimitiveStyleBuilder::new()
-            .fill_color(background_color)
-            .stroke_color(border_color)
-            .stroke_width(theme.spacing_xs())
-            .build();
-
-        let outline = Rectangle::new(self.pos + Point::new(2, 2), self.size - Size::new(4, 4));
-
-        outline.draw_styled(&outline_style, target)?;
+        Frame::themed(
+            self.bounding_box().offset(-(theme.spacing_xs() as i32)),
+            theme,
+            flags,
+            style.background,
+            style.border,
+        )
+        .draw(target)?;
         label.draw(target)?;
         Ok(())
     }
 }
-
-impl<M: Copy + Clone, D, T, C> Element<M, D, T, C> for Number<M>
-where
-    M: Copy + Clone,
-    D: DrawTarget<Color = C>,
-    C: PixelColor + Default + From<Rgb888>,
-    T: Theme<C>,
-{
-}
-impl<M> Dimensions for Number<M>
-where
-    M: Copy + Clone,
-{
-    fn bounding_box(&self) -> embedded_graphics::primitives::Rectangle {
-        Rectangle::new(self.pos, self.size)
-    }
-}
@@ -0,0 +1,416 @@
+use embedded_graphics::{
+    mono_font::MonoTextStyle, pixelcolor::Rgb888, prelude::*, primitives::Rectangle,
+};
+use embedded_text::{
+    TextBox,
+    alignment::{HorizontalAlignment, VerticalAlignment},
+    style::{HeightMode, TextBoxStyleBuilder, VerticalOverdraw},
+};
+
+use crate::{
+    InputEvent, Key, Response, StateFlags, StateManager, ThemedWidget, Widget,
+    i18n::Text,
+    layout::{Place, split},
+    screen::Element,
+    themes::{Style, Theme},
+    widgets::{frame::Frame, progress::Orientation},
+};
+
+/// The rows of an open popup, shared by [`PopupMenu`] and
+/// [`Dropdown`](crate::widgets::Dropdown)
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct List {
+    pub bounds: Rectangle,
+    pub rows: usize,
+}
+
+impl List {
+    pub fn row(&self, index: usize) -> Rectangle {
+        split(self.bounds, Orientation::Vertical, self.rows, index, 0)
+    }
+    pub fn row_at(&self, point: Point) -> Option<usize> {
+        (0..self.rows).find(|&index| self.row(index).contains(point))
+    }
+
+    /// Draw a panel with one label per row, the `highlighted` one in the
+    /// selected style. Labels are left aligned unless `style` says
+    /// otherwise.
+    pub fn draw<'a, D, T, C>(
+        &self,
+        target: &mut D,
+        theme: &T,
+        style: &Style<C>,
+        labels: impl IntoIterator<Item = &'a Text<'a>>,
+        highlighted: Option<usize>,
+    ) -> Result<(), D::Error>
+    where
+        C: PixelColor + Default + From<Rgb888>,
+        D: DrawTarget<Color = C>,
+        T: Theme<C>,
+    {
+        Frame::new(self.bounds)
+            .with_radius(theme.panel_corner_radius())
+            .with_fill(theme.panel_background_color())
+            .with_border(theme.panel_border_color(), theme.panel_border_width())
+            .with_shadow(Point::new(2, 2), theme.shadow_color())
+            .draw(target)?;
+
+        let style = match style.alignment {
+            Some(_) => *style,
+            None => style.with_alignment(HorizontalAlignment::Left),
+        };
+        for (index, label) in labels.into_iter().enumerate() {
+            let row = self.row(index);
+            let flags = if highlighted == Some(index) {
+                StateFlags::SELECTED
+            } else {
+                StateFlags::NONE
+            };
+            let resolved = style.resolve(theme, flags);
+            if flags.contains(StateFlags::SELECTED) {
+                Frame::new(row.offset(-(theme.panel_border_width() as i32)))
+                    .with_fill(resolved.background)
+                    .draw(target)?;
+            }
+            let textbox_style = TextBoxStyleBuilder::new()
+                .height_mode(HeightMode::Exact(VerticalOverdraw::Hidden))
+                .alignment(resolved.alignment)
+                .vertical_alignment(VerticalAlignment::Middle)
+                .build();
+            TextBox::with_textbox_style(
+                label.resolve(theme),
+                row.offset(-(resolved.padding as i32)),
+                MonoTextStyle::new(resolved.font, resolved.text),
+                textbox_style,
+            )
+            .draw(target)?;
+        }
+        Ok(())
+    }
+}
+
+/// The row after or before `highlighted`, the first or last one if none
+/// is highlighted yet. Doesn't wrap.
+pub(crate) fn step(highlighted: Option<usize>, rows: usize, down: bool) -> Option<usize> {
+    match (highlighted, down) {
+        _ if rows == 0 => None,
+        (None, true) => Some(0),
+        (None, false) => Some(rows - 1),
+        (Some(index), true) => Some((index + 1).min(rows - 1)),
+        (Some(index), false) => Some(index.saturating_sub(1)),
+    }
+}
+
+/// A list of actions that pops up over the other widgets, e.g. for a long
+/// press or a menu button.
+///
+/// The menu is closed until [`PopupMenu::open_at`] and takes no space on
+/// screen while closed. While open it is drawn as an
+/// [overlay](crate::Widget::overlay) and gets every pointer event: a tap
+/// on an item sends the item's message, a tap anywhere else dismisses the
+/// menu. Up and down move the highlight, enter picks the highlighted item
+/// and escape closes the menu.
+///
+/// ```
+/// # use embedded_graphics::{pixelcolor::Rgb888, prelude::*};
+/// # use embui::{InputEvent, Widget, widgets::PopupMenu};
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Message {
+///     Rename,
+///     Delete,
+/// }
+///
+/// let mut menu: PopupMenu<Message, Rgb888, 2> =
+///     PopupMenu::new([("Rename", Message::Rename), ("Delete", Message::Delete)]);
+/// menu.open_at(Point::new(10, 10));
+/// assert!(menu.overlay().is_some());
+///
+/// let delete = Point::new(20, 35);
+/// menu.handle_event(InputEvent::Touch(delete));
+/// let response = menu.handle_event(InputEvent::TouchRelease(delete));
+/// assert_eq!(response.message(), Some(Message::Delete));
+/// assert!(!menu.is_open());
+/// ```
+#[derive(Clone, Debug)]
+pub struct PopupMenu<'a, M, C, const N: usize>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    items: [(Text<'a>, M); N],
+    position: Point,
+    width: u32,
+    item_height: u32,
+    open: bool,
+    highlighted: Option<usize>,
+    style: Style<C>,
+    state_manager: StateManager,
+}
+
+impl<'a, M, C, const N: usize> PopupMenu<'a, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// A closed menu with one item per label and message
+    pub fn new(items: [(impl Into<Text<'a>>, M); N]) -> Self {
+        Self {
+            items: items.map(|(label, message)| (label.into(), message)),
+            position: Point::zero(),
+            width: 100,
+            item_height: 20,
+            open: false,
+            highlighted: None,
+            style: Style::new(),
+            state_manager: StateManager::new(),
+        }
+    }
+
+    pub fn with_width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+    pub fn with_item_height(mut self, height: u32) -> Self {
+        self.item_height = height;
+        self
+    }
+    /// Override theme colours or font of the items
+    pub fn with_style(mut self, style: Style<C>) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Open the menu with its top left corner at `position`
+    pub fn open_at(&mut self, position: Point) {
+        self.position = position;
+        self.open = true;
+        self.highlighted = None;
+        self.state_manager.mark_dirty();
+    }
+    pub fn close(&mut self) {
+        if self.open {
+            self.open = false;
+            self.state_manager.mark_dirty();
+        }
+    }
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+    /// The item under the pointer or picked with the keys
+    pub fn highlighted(&self) -> Option<usize> {
+        self.highlighted
+    }
+
+    fn list(&self) -> List {
+        List {
+            bounds: Rectangle::new(
+                self.position,
+                Size::new(self.width, self.item_height * N as u32),
+            ),
+            rows: N,
+        }
+    }
+    fn highlight(&mut self, index: Option<usize>) {
+        if index != self.highlighted {
+            self.highlighted = index;
+            self.state_manager.mark_dirty();
+        }
+    }
+    fn pick(&mut self, index: Option<usize>) -> Response<M> {
+        self.close();
+        Response::Consumed(index.map(|index| self.items[index].1))
+    }
+}
+
+impl<M, C, const N: usize> Widget<M> for PopupMenu<'_, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// The message of the highlighted item
+    fn to_message(&self) -> Option<M> {
+        self.highlighted.map(|index| self.items[index].1)
+    }
+    fn handle_event(&mut self, event: InputEvent) -> Response<M> {
+        let state = &mut self.state_manager;
+        if !self.open || !state.is_enabled() || !state.is_visible() {
+            return Response::NotChanged;
+        }
+        // While open the whole screen belongs to the menu, so a press
+        // anywhere is held until it is released
+        let pointer = !matches!(event, InputEvent::KeyPress(_) | InputEvent::Key(_));
+        let was_pressed = state.is_pressed();
+        state.handle_event(event, pointer);
+
+        let list = self.list();
+        match event {
+            InputEvent::Touch(point)
+            | InputEvent::TouchMove(point)
+            | InputEvent::MouseDown(point)
+            | InputEvent::MouseMove(point) => self.highlight(list.row_at(point)),
+            InputEvent::TouchRelease(point) | InputEvent::MouseUp(point) if was_pressed => {
+                return self.pick(list.row_at(point));
+            }
+            InputEvent::Key(Key::Up) => self.highlight(step(self.highlighted, N, false)),
+            InputEvent::Key(Key::Down) => self.highlight(step(self.highlighted, N, true)),
+            InputEvent::Key(Key::Enter) if self.highlighted.is_some() => {
+                return self.pick(self.highlighted);
+            }
+            InputEvent::Key(Key::Escape) => self.close(),
+            _ => {}
+        }
+        Response::Consumed(None)
+    }
+    fn overlay(&self) -> Option<Rectangle> {
+        self.open.then(|| self.list().bounds)
+    }
+    fn get_state_manager(&self) -> &StateManager {
+        &self.state_manager
+    }
+    fn get_state_manager_mut(&mut self) -> &mut StateManager {
+        &mut self.state_manager
+    }
+}
+
+impl<D, T, C, M, const N: usize> ThemedWidget<D, T, C> for PopupMenu<'_, M, C, N>
+where
+    C: PixelColor + Default + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    M: Copy,
+{
+    /// Nothing, the menu is only drawn as an overlay
+    fn draw_with_theme(&self, _target: &mut D, _theme: &T) -> Result<(), D::Error> {
+        Ok(())
+    }
+    fn draw_overlay(&self, target: &mut D, theme: &T) -> Result<(), D::Error> {
+        if !self.open {
+            return Ok(());
+        }
+        self.list().draw(
+            target,
+            theme,
+            &self.style,
+            self.items.iter().map(|(label, _)| label),
+            self.highlighted,
+        )
+    }
+}
+
+impl<M, D, T, C, const N: usize> Element<M, D, T, C> for PopupMenu<'_, M, C, N>
+where
+    M: Copy + Clone,
+    D: DrawTarget<Color = C>,
+    C: PixelColor + Default + From<Rgb888>,
+    T: Theme<C>,
+{
+}
+
+impl<M, C, const N: usize> Transform for PopupMenu<'_, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn translate(&self, by: Point) -> Self {
+        let mut moved = self.clone();
+        moved.translate_mut(by);
+        moved
+    }
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.position += by;
+        self
+    }
+}
+
+impl<M, C, const N: usize> Place for PopupMenu<'_, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// Open at the top left of `bounds` with its width
+    fn place(&mut self, bounds: Rectangle) {
+        self.position = bounds.top_left;
+        self.width = bounds.size.width;
+        self.state_manager.mark_dirty();
+    }
+}
+
+impl<M, C, const N: usize> Dimensions for PopupMenu<'_, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// The menu while open, nothing while closed
+    fn bounding_box(&self) -> Rectangle {
+        match self.overlay() {
+            Some(menu) => menu,
+            None => Rectangle::new(self.position, Size::zero()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{router::Router, widgets::Button};
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Message {
+        Copy,
+        Paste,
+        Pressed,
+    }
+
+    #[test]
+    fn tap_outside_dismisses() {
+        let mut button: Button<Message, Rgb888> = Button::new("B")
+            .on_press(Message::Pressed)
+            .with_position(Point::new(0, 50))
+            .with_size(Size::new(60, 14));
+        let mut menu: PopupMenu<Message, Rgb888, 2> =
+            PopupMenu::new([("Copy", Message::Copy), ("Paste", Message::Paste)]);
+        menu.open_at(Point::zero());
+        let mut router: Router = Router::new();
+        let mut messages: heapless::Vec<Message, 4> = heapless::Vec::new();
+
+        // The tap on the button only closes the menu
+        let outside = Point::new(5, 55);
+        for event in [
+            InputEvent::Touch(outside),
+            InputEvent::TouchRelease(outside),
+        ] {
+            router.route(
+                &mut [&mut button as &mut dyn Widget<Message>, &mut menu],
+                event,
+                |message, _| messages.push(message).unwrap(),
+            );
+        }
+        assert!(messages.is_empty());
+        assert!(!menu.is_open());
+        assert!(!button.get_state_manager().is_pressed());
+
+        // Closed, the menu is out of the way
+        router.route(
+            &mut [&mut button as &mut dyn Widget<Message>, &mut menu],
+            InputEvent::Touch(outside),
+            |message, _| messages.push(message).unwrap(),
+        );
+        assert_eq!(messages, [Message::Pressed]);
+    }
+
+    #[test]
+    fn keys_pick_items() {
+        let mut menu: PopupMenu<Message, Rgb888, 2> =
+            PopupMenu::new([("Copy", Message::Copy), ("Paste", Message::Paste)]);
+        menu.open_at(Point::zero());
+        let enter = InputEvent::Key(Key::Enter);
+        assert!(menu.handle_event(enter).message().is_none());
+        assert!(menu.is_open());
+
+        menu.handle_event(InputEvent::Key(Key::Up));
+        assert_eq!(menu.highlighted(), Some(1));
+        menu.handle_event(InputEvent::Key(Key::Up));
+        assert_eq!(menu.handle_event(enter).message(), Some(Message::Copy));
+        assert!(!menu.is_open());
+    }
+}
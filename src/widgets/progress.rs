@@ -0,0 +1,269 @@
+use core::marker::PhantomData;
+
+use embedded_graphics::{
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle, StyledDrawable},
+};
+
+use crate::{
+    StateManager, Theme, ThemedWidget, Widget,
+    binding::{Binding, Bound},
+    widgets::{chart::Thresholds, frame::Frame},
+};
+
+/// Direction a bar fills in.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Orientation {
+    /// Fills left to right
+    #[default]
+    Horizontal,
+    /// Fills bottom to top
+    Vertical,
+}
+
+/// Length of the part of `total` pixels that represents `value` in `min..=max`.
+fn scaled(value: i32, min: i32, max: i32, total: u32) -> u32 {
+    let span = (max as i64 - min as i64).max(1);
+    let offset = (value as i64).clamp(min as i64, max as i64) - min as i64;
+    (offset * total as i64 / span) as u32
+}
+
+/// The part of `area` that a bar filled `length` pixels along `orientation` covers.
+fn filled(area: Rectangle, length: u32, orientation: Orientation) -> Rectangle {
+    match orientation {
+        Orientation::Horizontal => {
+            Rectangle::new(area.top_left, Size::new(length, area.size.height))
+        }
+        Orientation::Vertical => Rectangle::new(
+            area.top_left + Point::new(0, (area.size.height - length) as i32),
+            Size::new(area.size.width, length),
+        ),
+    }
+}
+
+/// A linear progress bar.
+///
+/// A determinate bar shows `value` within `min..=max`. An indeterminate bar
+/// shows a chunk that moves along the track every [`tick`](Self::tick), for
+/// operations whose duration is unknown.
+#[derive(Clone, Widget)]
+pub struct ProgressBar<'a, M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    ph: PhantomData<M>,
+    #[binding]
+    value: Bound<'a, i32>,
+    min: i32,
+    max: i32,
+    indeterminate: bool,
+    phase: u32,
+    orientation: Orientation,
+    color: Option<C>,
+    thresholds: Option<Thresholds>,
+    #[position]
+    pos: Point,
+    #[size]
+    size: Size,
+    #[state]
+    state_manager: StateManager,
+}
+
+impl<'a, M, C> ProgressBar<'a, M, C>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// A determinate bar going from 0 to 100
+    pub fn new(pos: Point, size: Size) -> Self {
+        Self {
+            ph: PhantomData,
+            value: Bound::new(0),
+            min: 0,
+            max: 100,
+            indeterminate: false,
+            phase: 0,
+            orientation: Orientation::Horizontal,
+            color: None,
+            thresholds: None,
+            pos,
+            size,
+            state_manager: StateManager::read_only(),
+        }
+    }
+    pub fn indeterminate(pos: Point, size: Size) -> Self {
+        let mut bar = Self::new(pos, size);
+        bar.indeterminate = true;
+        bar
+    }
+
+    /// The values of an empty and a full bar, in either order
+    pub fn with_range(mut self, min: i32, max: i32) -> Self {
+        self.min = min.min(max);
+        self.max = min.max(max);
+        self
+    }
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+    pub fn with_color(mut self, color: C) -> Self {
+        self.color = Some(color);
+        self
+    }
+    /// Colour the bar by its value instead of using a single colour
+    pub fn with_thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.thresholds = Some(thresholds);
+        self
+    }
+
+    /// Show a value owned by the model instead of the widget
+    pub fn bind(mut self, binding: impl Into<Binding<'a, i32>>) -> Self {
+        self.value.bind(binding.into());
+        self
+    }
+    /// Set the value, writing through to a bound [`Signal`](crate::Signal)
+    pub fn set(&mut self, value: i32) {
+        self.value.set(value);
+    }
+    pub fn get(&self) -> i32 {
+        self.value.get()
+    }
+    pub fn set_indeterminate(&mut self, indeterminate: bool) {
+        self.indeterminate = indeterminate;
+        self.phase = 0;
+        self.state_manager.mark_dirty();
+    }
+    pub fn is_indeterminate(&self) -> bool {
+        self.indeterminate
+    }
+    /// Advance the indeterminate animation by one frame
+    pub fn tick(&mut self) {
+        self.phase = self.phase.wrapping_add(1);
+        if self.indeterminate {
+            self.state_manager.mark_dirty();
+        }
+    }
+
+    fn track(&self) -> (Rectangle, u32) {
+        let area = Rectangle::new(self.pos, self.size).offset(-1);
+        let length = match self.orientation {
+            Orientation::Horizontal => area.size.width,
+            Orientation::Vertical => area.size.height,
+        };
+        (area, length)
+    }
+}
+
+impl<D, T, C, M> ThemedWidget<D, T, C> for ProgressBar<'_, M, C>
+where
+    C: PixelColor + Default + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    M: Copy,
+{
+    fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), <D as DrawTarget>::Error> {
+        Frame::new(self.bounding_box())
+            .with_fill(theme.meter_track_color())
+            .with_border(theme.meter_border_color(), 1)
+            .draw(target)?;
+
+        let (track, length) = self.track();
+        let fill = match self.thresholds {
+            Some(thresholds) if !self.indeterminate => thresholds.color(theme, self.value.get()),
+            _ => self.color.unwrap_or_else(|| theme.meter_fill_color()),
+        };
+        let fill_style = PrimitiveStyle::with_fill(fill);
+
+        if self.indeterminate {
+            // A quarter-length chunk enters on one side and leaves on the other
+            let chunk = (length / 4).max(1);
+            let travel = length + chunk;
+            let head = self.phase.wrapping_mul(theme.spacing_xs()) % travel;
+            let start = head.saturating_sub(chunk);
+            let end = head.min(length);
+            let part = match self.orientation {
+                Orientation::Horizontal => Rectangle::new(
+                    track.top_left + Point::new(start as i32, 0),
+                    Size::new(end - start, track.size.height),
+                ),
+                Orientation::Vertical => Rectangle::new(
+                    track.top_left + Point::new(0, (length - end) as i32),
+                    Size::new(track.size.width, end - start),
+                ),
+            };
+            part.draw_styled(&fill_style, target)?;
+        } else {
+            let done = scaled(self.value.get(), self.min, self.max, length);
+            filled(track, done, self.orientation).draw_styled(&fill_style, target)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::mock_display::MockDisplay;
+
+    use super::*;
+    use crate::{InputEvent, Response, WidgetState, themes::DefaultTheme};
+
+    #[derive(Clone, Copy, Debug)]
+    enum Message {}
+
+    #[test]
+    fn fill_follows_value() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let mut bar: ProgressBar<Message, Rgb888> =
+            ProgressBar::new(Point::zero(), Size::new(22, 4)).with_range(0, 10);
+        bar.set(5);
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        bar.draw_with_theme(&mut display, &theme).unwrap();
+        // Half of the 20 pixel track inside the border
+        assert_eq!(
+            display.get_pixel(Point::new(10, 1)),
+            Some(theme.meter_fill_color())
+        );
+        assert_eq!(
+            display.get_pixel(Point::new(11, 1)),
+            Some(theme.meter_track_color())
+        );
+    }
+
+    #[test]
+    fn reversed_range_is_swapped() {
+        let mut bar: ProgressBar<Message, Rgb888> =
+            ProgressBar::new(Point::zero(), Size::new(22, 4)).with_range(10, 0);
+        bar.set(5);
+        assert_eq!(scaled(bar.get(), bar.min, bar.max, 20), 10);
+    }
+
+    #[test]
+    fn indeterminate_runs_forever() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let mut bar: ProgressBar<Message, Rgb888> =
+            ProgressBar::new(Point::zero(), Size::new(22, 4));
+        bar.set_indeterminate(true);
+        bar.phase = u32::MAX;
+        bar.tick();
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        bar.draw_with_theme(&mut display, &theme).unwrap();
+    }
+
+    #[test]
+    fn never_becomes_interactive() {
+        let mut bar: ProgressBar<Message, Rgb888> =
+            ProgressBar::new(Point::zero(), Size::new(32, 8));
+        let inside = Point::new(4, 4);
+        for event in [InputEvent::MouseMove(inside), InputEvent::MouseDown(inside)] {
+            assert!(matches!(bar.handle_event(event), Response::NotChanged));
+        }
+        assert_eq!(bar.get_state(), WidgetState::Normal);
+    }
+}
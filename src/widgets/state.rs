@@ -27,11 +27,120 @@ impl WidgetState {
     }
 }
 
+/// A name for a widget chosen by the application, see
+/// [`Widget::with_id`](crate::Widget::with_id).
+///
+/// Unlike an index into the view, it stays the same when widgets are
+/// added, removed or reordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WidgetId(pub u16);
+
+/// Everything that is true about a widget at once.
+///
+/// A widget can be focused, hovered and pressed at the same time. The
+/// single [`WidgetState`] that most widgets draw with is the most prominent
+/// flag, see [`StateFlags::state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct StateFlags(u8);
+
+impl StateFlags {
+    pub const NONE: Self = Self(0);
+    /// The pointer is over the widget
+    pub const HOVERED: Self = Self(1 << 0);
+    /// A mouse button or finger went down on the widget and is still down
+    pub const PRESSED: Self = Self(1 << 1);
+    /// The widget receives key presses
+    pub const FOCUSED: Self = Self(1 << 2);
+    pub const DISABLED: Self = Self(1 << 3);
+    /// A toggle that is on
+    pub const CHECKED: Self = Self(1 << 4);
+    /// The current entry of a list or group
+    pub const SELECTED: Self = Self(1 << 5);
+
+    pub const fn contains(self, flags: Self) -> bool {
+        self.0 & flags.0 == flags.0
+    }
+    pub const fn union(self, flags: Self) -> Self {
+        Self(self.0 | flags.0)
+    }
+    pub const fn difference(self, flags: Self) -> Self {
+        Self(self.0 & !flags.0)
+    }
+    pub fn set(&mut self, flags: Self, on: bool) {
+        *self = if on {
+            self.union(flags)
+        } else {
+            self.difference(flags)
+        };
+    }
+
+    /// The most prominent flag: disabled, then pressed, focused and hovered
+    pub const fn state(self) -> WidgetState {
+        if self.contains(Self::DISABLED) {
+            WidgetState::Disabled
+        } else if self.contains(Self::PRESSED) {
+            WidgetState::Pressed
+        } else if self.contains(Self::FOCUSED) {
+            WidgetState::Focused
+        } else if self.contains(Self::HOVERED) {
+            WidgetState::Hovered
+        } else {
+            WidgetState::Normal
+        }
+    }
+}
+
+impl core::ops::BitOr for StateFlags {
+    type Output = Self;
+
+    fn bitor(self, flags: Self) -> Self {
+        self.union(flags)
+    }
+}
+
+impl From<WidgetState> for StateFlags {
+    fn from(state: WidgetState) -> Self {
+        match state {
+            WidgetState::Normal => Self::NONE,
+            WidgetState::Hovered => Self::HOVERED,
+            WidgetState::Pressed => Self::PRESSED,
+            WidgetState::Focused => Self::FOCUSED,
+            WidgetState::Disabled => Self::DISABLED,
+        }
+    }
+}
+
+/// Tracks the [`StateFlags`] of a widget and whether it needs a redraw.
+///
+/// Input changes the flags of an enabled, visible and interactive widget
+/// like this, `inside` meaning the event's point is within the widget:
+///
+/// | Event          | Flags afterwards                                 |
+/// |----------------|--------------------------------------------------|
+/// | `MouseMove`    | hovered if `inside`, unchanged while pressed     |
+/// | `MouseEnter`   | hovered, unchanged while pressed                 |
+/// | `MouseLeave`   | not hovered, unchanged while pressed             |
+/// | `MouseDown`    | hovered and pressed if `inside`, else unchanged  |
+/// | `MouseUp`      | not pressed, hovered if `inside`                 |
+/// | `Touch`        | pressed if `inside`, else unchanged              |
+/// | `TouchMove`    | unchanged                                        |
+/// | `TouchRelease` | neither pressed nor hovered, fingers don't hover |
+/// | `KeyPress`     | unchanged                                        |
+/// | `Key`          | unchanged                                        |
+///
+/// Focus, checked and selected never change with input, the runtime and the
+/// widget set them. Disabling or hiding a widget drops hover and press,
+/// hiding also drops focus. Disabled, hidden and read-only widgets ignore
+/// input.
 #[derive(Debug, Clone, Copy)]
 pub struct StateManager {
-    current_state: WidgetState,
-    previous_state: WidgetState,
-    enabled: bool,
+    id: Option<WidgetId>,
+    flags: StateFlags,
+    previous: StateFlags,
+    visible: bool,
+    interactive: bool,
+    dirty: bool,
+    cursor_visible: bool,
 }
 
 impl Default for StateManager {
@@ -43,127 +152,232 @@ impl Default for StateManager {
 impl StateManager {
     pub fn new() -> Self {
         Self {
-            current_state: WidgetState::default(),
-            previous_state: WidgetState::default(),
-            enabled: true,
+            id: None,
+            flags: StateFlags::NONE,
+            previous: StateFlags::NONE,
+            visible: true,
+            interactive: true,
+            dirty: true,
+            cursor_visible: true,
         }
     }
 
+    /// State manager for display-only widgets: input never moves it out of
+    /// `Normal`, only enabling and disabling changes its state
+    pub fn read_only() -> Self {
+        Self {
+            interactive: false,
+            ..Self::new()
+        }
+    }
+
+    pub fn id(&self) -> Option<WidgetId> {
+        self.id
+    }
+    pub fn set_id(&mut self, id: WidgetId) {
+        self.id = Some(id);
+    }
+
     /// Get current state
     pub fn current_state(&self) -> WidgetState {
-        self.current_state
+        self.flags.state()
     }
 
     /// Get previous state
     pub fn previous_state(&self) -> WidgetState {
-        self.previous_state
+        self.previous.state()
+    }
+
+    pub fn flags(&self) -> StateFlags {
+        self.flags
+    }
+    pub fn is_hovered(&self) -> bool {
+        self.flags.contains(StateFlags::HOVERED)
+    }
+    pub fn is_pressed(&self) -> bool {
+        self.flags.contains(StateFlags::PRESSED)
+    }
+    pub fn is_focused(&self) -> bool {
+        self.flags.contains(StateFlags::FOCUSED)
+    }
+    pub fn is_checked(&self) -> bool {
+        self.flags.contains(StateFlags::CHECKED)
+    }
+    pub fn is_selected(&self) -> bool {
+        self.flags.contains(StateFlags::SELECTED)
     }
 
     /// Check if widget is enabled
     pub fn is_enabled(&self) -> bool {
-        self.enabled
+        !self.flags.contains(StateFlags::DISABLED)
     }
-    /// Set enabled state
+    /// Check if widget reacts to input at all
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+    /// Set enabled state. Disabling drops hover and press, focus is kept
+    /// for when the widget is enabled again.
     pub fn set_enabled(&mut self, enabled: bool) -> bool {
-        if self.enabled == enabled {
+        let mut flags = self.flags;
+        flags.set(StateFlags::DISABLED, !enabled);
+        if !enabled {
+            flags.set(StateFlags::HOVERED | StateFlags::PRESSED, false);
+        }
+        self.update(flags)
+    }
+    /// Check if widget is drawn and takes input
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+    /// Show or hide the widget. Hiding drops hover, press and focus.
+    pub fn set_visible(&mut self, visible: bool) -> bool {
+        if self.visible == visible {
             return false;
         }
-
-        self.enabled = enabled;
-        if enabled {
-            // Re-enable: go back to normal unless we were focused
-            let target = if self.previous_state == WidgetState::Focused {
-                WidgetState::Focused
-            } else {
-                WidgetState::Normal
-            };
-            self.set_state(target)
-        } else {
-            // Disable: remember current state and go to disabled
-            self.set_state(WidgetState::Disabled)
+        self.visible = visible;
+        if !visible {
+            self.update(
+                self.flags
+                    .difference(StateFlags::HOVERED | StateFlags::PRESSED | StateFlags::FOCUSED),
+            );
+        }
+        self.dirty = true;
+        true
+    }
+    /// Give or take the keyboard focus. Read-only widgets never take it.
+    pub fn set_focused(&mut self, focused: bool) -> bool {
+        if focused && !self.interactive {
+            return false;
         }
+        let mut flags = self.flags;
+        flags.set(StateFlags::FOCUSED, focused);
+        self.update(flags)
     }
+    pub fn set_checked(&mut self, checked: bool) -> bool {
+        let mut flags = self.flags;
+        flags.set(StateFlags::CHECKED, checked);
+        self.update(flags)
+    }
+    pub fn set_selected(&mut self, selected: bool) -> bool {
+        let mut flags = self.flags;
+        flags.set(StateFlags::SELECTED, selected);
+        self.update(flags)
+    }
+    /// Move to `new_state` as if it was the only flag that matters:
+    /// `Normal` drops hover, press and focus, `Hovered` drops press and
+    /// `Disabled` disables the widget. Disabled widgets only leave
+    /// `Disabled` through [`set_enabled`](Self::set_enabled).
     pub fn set_state(&mut self, new_state: WidgetState) -> bool {
-        // Don't allow changes from disabled unless enabling
-        if self.current_state == WidgetState::Disabled
-            && new_state != WidgetState::Disabled
-            && !self.enabled
-        {
+        if !self.is_enabled() {
             return false;
         }
-
-        if self.current_state == new_state {
+        // Read-only widgets only ever toggle between normal and disabled
+        if !self.interactive && !matches!(new_state, WidgetState::Normal | WidgetState::Disabled) {
             return false;
         }
 
-        self.previous_state = self.current_state;
-        self.current_state = new_state;
-        true
+        let mut flags = self.flags;
+        match new_state {
+            WidgetState::Normal => {
+                flags.set(
+                    StateFlags::HOVERED | StateFlags::PRESSED | StateFlags::FOCUSED,
+                    false,
+                );
+            }
+            WidgetState::Hovered => {
+                flags.set(StateFlags::HOVERED, true);
+                flags.set(StateFlags::PRESSED, false);
+            }
+            WidgetState::Pressed => flags.set(StateFlags::PRESSED, true),
+            WidgetState::Focused => flags.set(StateFlags::FOCUSED, true),
+            WidgetState::Disabled => return self.set_enabled(false),
+        }
+        self.update(flags)
     }
     // Handle input event and potentially transition state
     pub fn handle_event(&mut self, event: InputEvent, contains_point: bool) -> bool {
-        if !self.enabled {
+        if !self.is_enabled() || !self.interactive || !self.visible {
             return false;
         }
 
-        let new_state = match (self.current_state, event) {
-            // Mouse/touch interactions
-            (WidgetState::Normal, InputEvent::MouseMove(_)) if contains_point => {
-                Some(WidgetState::Hovered)
-            }
-            (WidgetState::Hovered, InputEvent::MouseMove(_)) if !contains_point => {
-                Some(WidgetState::Normal)
+        let mut flags = self.flags;
+        match event {
+            // The press keeps the pointer until the button goes up
+            InputEvent::MouseMove(_) if !self.is_pressed() => {
+                flags.set(StateFlags::HOVERED, contains_point);
             }
-            (WidgetState::Hovered, InputEvent::MouseDown(_)) if contains_point => {
-                Some(WidgetState::Pressed)
+            InputEvent::MouseEnter(_) if !self.is_pressed() => {
+                flags.set(StateFlags::HOVERED, true);
             }
-            (WidgetState::Normal, InputEvent::MouseDown(_)) if contains_point => {
-                Some(WidgetState::Pressed)
+            InputEvent::MouseLeave(_) if !self.is_pressed() => {
+                flags.set(StateFlags::HOVERED, false);
             }
-            (WidgetState::Pressed, InputEvent::MouseUp(_)) => {
-                if contains_point {
-                    Some(WidgetState::Hovered)
-                } else {
-                    Some(WidgetState::Normal)
-                }
-            }
-            (WidgetState::Normal, InputEvent::Touch(_)) if contains_point => {
-                Some(WidgetState::Pressed)
+            InputEvent::MouseDown(_) if contains_point => {
+                flags.set(StateFlags::HOVERED | StateFlags::PRESSED, true);
             }
-            (WidgetState::Pressed, InputEvent::TouchRelease(_)) => {
-                Some(WidgetState::Normal) // Touch usually doesn't hover
-            }
-
-            // Focused state handling - simple approach
-            (WidgetState::Focused, InputEvent::MouseMove(_)) if contains_point => {
-                Some(WidgetState::Hovered)
+            InputEvent::MouseUp(_) => {
+                flags.set(StateFlags::PRESSED, false);
+                flags.set(StateFlags::HOVERED, contains_point);
             }
-            (WidgetState::Focused, InputEvent::MouseMove(_)) if !contains_point => {
-                Some(WidgetState::Normal)
+            InputEvent::Touch(_) if contains_point => flags.set(StateFlags::PRESSED, true),
+            // Touch usually doesn't hover
+            InputEvent::TouchRelease(_) => {
+                flags.set(StateFlags::HOVERED | StateFlags::PRESSED, false);
             }
-
-            _ => None,
-        };
-
-        if let Some(state) = new_state {
-            self.set_state(state)
-        } else {
-            false
+            _ => {}
         }
+        self.update(flags)
     }
-    /// Reset to normal state
+    /// Reset to normal state, keeping only whether the widget is enabled
     pub fn reset(&mut self) {
-        self.current_state = if self.enabled {
-            WidgetState::Normal
-        } else {
-            WidgetState::Disabled
-        };
-        self.previous_state = self.current_state;
+        self.flags = self.flags.difference(
+            StateFlags::HOVERED
+                | StateFlags::PRESSED
+                | StateFlags::FOCUSED
+                | StateFlags::CHECKED
+                | StateFlags::SELECTED,
+        );
+        self.previous = self.flags;
+        self.dirty = true;
+    }
+
+    fn update(&mut self, flags: StateFlags) -> bool {
+        if flags == self.flags {
+            return false;
+        }
+        self.previous = self.flags;
+        self.flags = flags;
+        self.dirty = true;
+        true
     }
 
     /// Check if state has changed since last check
     pub fn state_changed(&self) -> bool {
-        self.current_state != self.previous_state
+        self.flags != self.previous
+    }
+
+    /// Check if the widget needs to be redrawn
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+    /// Request a redraw, e.g. after the widget's content changed
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+    /// Call once the widget has been drawn
+    pub fn clear_dirty(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Whether a text cursor is in the visible half of its blink
+    pub fn cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        if self.cursor_visible != visible {
+            self.cursor_visible = visible;
+            self.dirty = true;
+        }
     }
 }
 
@@ -206,4 +420,155 @@ mod tests {
         assert!(sm.handle_event(InputEvent::MouseUp(Point::zero()), true));
         assert_eq!(sm.current_state(), WidgetState::Hovered);
     }
+
+    #[test]
+    fn read_only_ignores_input() {
+        let mut sm = StateManager::read_only();
+        assert!(!sm.is_interactive());
+
+        assert!(!sm.handle_event(InputEvent::MouseMove(Point::zero()), true));
+        assert!(!sm.handle_event(InputEvent::MouseDown(Point::zero()), true));
+        assert!(!sm.set_state(WidgetState::Focused));
+        assert_eq!(sm.current_state(), WidgetState::Normal);
+
+        // Disabling still works
+        assert!(sm.set_enabled(false));
+        assert_eq!(sm.current_state(), WidgetState::Disabled);
+        assert!(sm.set_enabled(true));
+        assert_eq!(sm.current_state(), WidgetState::Normal);
+    }
+
+    #[test]
+    fn hidden_ignores_input() {
+        let mut state = StateManager::new();
+        state.handle_event(InputEvent::MouseMove(Point::zero()), true);
+        assert!(state.set_visible(false));
+        assert_eq!(state.current_state(), WidgetState::Normal);
+        assert!(!state.handle_event(InputEvent::MouseDown(Point::zero()), true));
+        assert!(state.is_dirty());
+    }
+
+    #[test]
+    fn state_changes_mark_dirty() {
+        let mut sm = StateManager::new();
+        assert!(sm.is_dirty());
+        sm.clear_dirty();
+
+        assert!(!sm.set_state(WidgetState::Normal));
+        assert!(!sm.is_dirty());
+        assert!(sm.handle_event(InputEvent::MouseMove(Point::zero()), true));
+        assert!(sm.is_dirty());
+    }
+
+    #[test]
+    fn focus_survives_hover() {
+        let mut sm = StateManager::new();
+        assert!(sm.set_focused(true));
+        assert!(sm.handle_event(InputEvent::MouseMove(Point::zero()), true));
+        assert!(sm.is_hovered());
+        assert_eq!(sm.current_state(), WidgetState::Focused);
+
+        assert!(sm.handle_event(InputEvent::MouseDown(Point::zero()), true));
+        assert_eq!(
+            sm.flags(),
+            StateFlags::FOCUSED | StateFlags::HOVERED | StateFlags::PRESSED
+        );
+        assert_eq!(sm.current_state(), WidgetState::Pressed);
+
+        // Released outside the widget
+        assert!(sm.handle_event(InputEvent::MouseUp(Point::zero()), false));
+        assert_eq!(sm.flags(), StateFlags::FOCUSED);
+    }
+
+    /// xorshift, so the random sequences are the same on every run
+    struct Rng(u32);
+
+    impl Rng {
+        fn next(&mut self, below: u32) -> u32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            self.0 % below
+        }
+    }
+
+    /// A random event, or a random call that changes the state directly.
+    /// Returns whether input was given.
+    fn step(sm: &mut StateManager, rng: &mut Rng) -> Option<(InputEvent, bool)> {
+        let point = Point::zero();
+        let inside = rng.next(2) == 0;
+        let event = match rng.next(13) {
+            0 => InputEvent::MouseMove(point),
+            1 => InputEvent::MouseDown(point),
+            2 => InputEvent::MouseUp(point),
+            3 => InputEvent::Touch(point),
+            4 => InputEvent::TouchRelease(point),
+            5 => InputEvent::KeyPress(' '),
+            6 => InputEvent::MouseEnter(point),
+            7 => InputEvent::MouseLeave(point),
+            8 => InputEvent::TouchMove(point),
+            9 => {
+                sm.set_enabled(rng.next(4) != 0);
+                return None;
+            }
+            10 => {
+                sm.set_visible(rng.next(4) != 0);
+                return None;
+            }
+            11 => {
+                sm.set_focused(inside);
+                return None;
+            }
+            _ => {
+                sm.set_checked(inside);
+                return None;
+            }
+        };
+        Some((event, inside))
+    }
+
+    #[test]
+    fn random_sequences_keep_invariants() {
+        let pointer = StateFlags::HOVERED | StateFlags::PRESSED;
+        let kept = StateFlags::FOCUSED | StateFlags::CHECKED | StateFlags::DISABLED;
+        for seed in 1..200 {
+            let mut rng = Rng(seed * 7919);
+            let mut sm = if seed % 5 == 0 {
+                StateManager::read_only()
+            } else {
+                StateManager::new()
+            };
+            for _ in 0..100 {
+                let before = sm.flags();
+                sm.clear_dirty();
+                let Some((event, inside)) = step(&mut sm, &mut rng) else {
+                    continue;
+                };
+                let changed = sm.handle_event(event, inside);
+                let after = sm.flags();
+
+                // Input only ever touches hover and press
+                assert_eq!(after.difference(pointer), before.difference(pointer));
+                assert_eq!(changed, before != after);
+                assert_eq!(changed, sm.is_dirty());
+                if !sm.is_enabled() || !sm.is_visible() || !sm.is_interactive() {
+                    assert!(!changed);
+                    assert!(!after.contains(StateFlags::HOVERED));
+                    assert!(!after.contains(StateFlags::PRESSED));
+                }
+                if matches!(event, InputEvent::MouseUp(_) | InputEvent::TouchRelease(_)) {
+                    assert!(!sm.is_pressed());
+                }
+                if !sm.is_interactive() {
+                    assert!(after.difference(kept) == StateFlags::NONE && !sm.is_focused());
+                }
+                let state = sm.current_state();
+                assert_eq!(state == WidgetState::Disabled, !sm.is_enabled());
+                assert_eq!(
+                    state == WidgetState::Pressed,
+                    sm.is_enabled() && sm.is_pressed()
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,399 @@
+use embedded_graphics::{
+    mono_font::MonoTextStyle, pixelcolor::Rgb888, prelude::*, primitives::Rectangle,
+};
+use embedded_text::{
+    TextBox,
+    alignment::{HorizontalAlignment, VerticalAlignment},
+    style::{HeightMode, TextBoxStyleBuilder, VerticalOverdraw},
+};
+
+use crate::{
+    InputEvent, Key, Response, StateFlags, StateManager, ThemedWidget, Widget,
+    i18n::Text,
+    layout::{Place, split},
+    screen::Element,
+    themes::{Style, Theme},
+    widgets::{frame::Frame, progress::Orientation},
+};
+
+/// A tab bar over a content area that shows one page at a time.
+///
+/// The pages are the children of the tabs in a [`tree`](crate::tree), which
+/// shows only the page of the active tab, so the others are neither drawn
+/// nor get events. After [`Tabs::set_active`], show its page with
+/// `show_child`. Place the pages in [`Tabs::content`].
+///
+/// The active tab changes by touching its header, with the left and right
+/// keys while the tabs are focused, or by swiping across the content
+/// area. Swipes over a page reach the tabs when nothing on the page
+/// consumed them, whether a tree [dispatches](crate::tree::Arena::dispatch)
+/// them or the [`router`](crate::router) of a [`Runtime`](crate::Runtime).
+///
+/// ```
+/// # use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+/// # use embui::{InputEvent, Widget, widgets::Tabs};
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Message {
+///     Tab(usize),
+/// }
+///
+/// let mut tabs: Tabs<Message, Rgb888, 3> = Tabs::new(
+///     Rectangle::new(Point::zero(), Size::new(90, 60)),
+///     ["Network", "Display", "About"],
+/// )
+/// .on_tab_changed(Message::Tab);
+///
+/// let response = tabs.handle_event(InputEvent::Touch(Point::new(45, 5)));
+/// assert_eq!(response.message(), Some(Message::Tab(1)));
+/// assert_eq!(tabs.content().top_left, Point::new(0, 20));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Tabs<'a, M, C, const N: usize>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    labels: [Text<'a>; N],
+    active: usize,
+    bar_height: u32,
+    on_tab_changed: Option<fn(usize) -> M>,
+    swipe_from: Option<Point>,
+    bounds: Rectangle,
+    style: Style<C>,
+    state_manager: StateManager,
+}
+
+impl<'a, M, C, const N: usize> Tabs<'a, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// Tabs filling `bounds`, one per label, with the first one active
+    pub fn new(bounds: Rectangle, labels: [impl Into<Text<'a>>; N]) -> Self {
+        Self {
+            labels: labels.map(Into::into),
+            active: 0,
+            bar_height: 20,
+            on_tab_changed: None,
+            swipe_from: None,
+            bounds,
+            style: Style::new(),
+            state_manager: StateManager::new(),
+        }
+    }
+
+    /// Send the message for the new index whenever the active tab changes
+    pub fn on_tab_changed(mut self, message: fn(usize) -> M) -> Self {
+        self.on_tab_changed = Some(message);
+        self
+    }
+    pub fn with_bar_height(mut self, height: u32) -> Self {
+        self.bar_height = height;
+        self
+    }
+    /// Override theme colours or font of the headers
+    pub fn with_style(mut self, style: Style<C>) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn active(&self) -> usize {
+        self.active
+    }
+    /// Switch tabs without sending a message
+    pub fn set_active(&mut self, index: usize) {
+        if index < N && index != self.active {
+            self.active = index;
+            self.state_manager.mark_dirty();
+        }
+    }
+
+    /// The area of the tab bar
+    pub fn bar(&self) -> Rectangle {
+        let height = self.bar_height.min(self.bounds.size.height);
+        Rectangle::new(
+            self.bounds.top_left,
+            Size::new(self.bounds.size.width, height),
+        )
+    }
+    /// The header of tab `index`
+    pub fn header(&self, index: usize) -> Rectangle {
+        split(self.bar(), Orientation::Horizontal, N, index, 0)
+    }
+    /// The area below the tab bar, where the pages go
+    pub fn content(&self) -> Rectangle {
+        let bar = self.bar().size.height;
+        Rectangle::new(
+            self.bounds.top_left + Point::new(0, bar as i32),
+            Size::new(self.bounds.size.width, self.bounds.size.height - bar),
+        )
+    }
+
+    fn tab_at(&self, point: Point) -> Option<usize> {
+        (0..N).find(|&index| self.header(index).contains(point))
+    }
+    /// The tab a swipe from `from` to `to` leads to. Swipes go at least a
+    /// quarter of the width and more sideways than up or down.
+    fn swiped(&self, from: Point, to: Point) -> Option<usize> {
+        let delta = to - from;
+        if delta.x.unsigned_abs() < self.bounds.size.width / 4 || delta.x.abs() <= delta.y.abs() {
+            return None;
+        }
+        // Swiping left pulls in the next page
+        if delta.x < 0 {
+            Some(self.active + 1).filter(|&index| index < N)
+        } else {
+            self.active.checked_sub(1)
+        }
+    }
+}
+
+impl<M, C, const N: usize> Widget<M> for Tabs<'_, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    /// The tab-changed message for the active tab
+    fn to_message(&self) -> Option<M> {
+        self.on_tab_changed.map(|message| message(self.active))
+    }
+    fn active_child(&self) -> Option<usize> {
+        Some(self.active)
+    }
+    fn handle_event(&mut self, event: InputEvent) -> Response<M> {
+        let state = &mut self.state_manager;
+        if !state.is_enabled() || !state.is_visible() {
+            return Response::NotChanged;
+        }
+        let inside = match event {
+            InputEvent::Touch(point)
+            | InputEvent::TouchMove(point)
+            | InputEvent::TouchRelease(point)
+            | InputEvent::MouseMove(point)
+            | InputEvent::MouseEnter(point)
+            | InputEvent::MouseLeave(point)
+            | InputEvent::MouseDown(point)
+            | InputEvent::MouseUp(point) => self.bounds.contains(point),
+            InputEvent::KeyPress(_) | InputEvent::Key(_) => false,
+        };
+        let was_pressed = state.is_pressed();
+        let state_changed = state.handle_event(event, inside);
+        let focused = state.is_focused();
+
+        let tab = match event {
+            InputEvent::Touch(point) | InputEvent::MouseDown(point) if inside => {
+                let tab = self.tab_at(point);
+                if tab.is_none() {
+                    self.swipe_from = Some(point);
+                }
+                tab
+            }
+            InputEvent::TouchRelease(point) | InputEvent::MouseUp(point) => self
+                .swipe_from
+                .take()
+                .and_then(|from| self.swiped(from, point)),
+            InputEvent::Key(Key::Left) if focused => self.active.checked_sub(1),
+            InputEvent::Key(Key::Right) if focused => {
+                Some(self.active + 1).filter(|&index| index < N)
+            }
+            _ => None,
+        };
+
+        match tab {
+            Some(index) if index != self.active => {
+                self.set_active(index);
+                Response::Consumed(self.to_message())
+            }
+            Some(_) => Response::Consumed(None),
+            None if was_pressed || self.state_manager.is_pressed() => Response::Consumed(None),
+            None if state_changed => Response::Changed(None),
+            None => Response::NotChanged,
+        }
+    }
+    fn get_state_manager(&self) -> &StateManager {
+        &self.state_manager
+    }
+    fn get_state_manager_mut(&mut self) -> &mut StateManager {
+        &mut self.state_manager
+    }
+}
+
+impl<D, T, C, M, const N: usize> ThemedWidget<D, T, C> for Tabs<'_, M, C, N>
+where
+    C: PixelColor + Default + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+    T: Theme<C>,
+    M: Copy,
+{
+    fn draw_with_theme(&self, target: &mut D, theme: &T) -> Result<(), <D as DrawTarget>::Error> {
+        Frame::new(self.content())
+            .with_radius(theme.panel_corner_radius())
+            .with_fill(theme.panel_background_color())
+            .with_border(theme.panel_border_color(), theme.panel_border_width())
+            .draw(target)?;
+
+        let flags = self.state_flags();
+        let textbox_style = TextBoxStyleBuilder::new()
+            .height_mode(HeightMode::Exact(VerticalOverdraw::Hidden))
+            .alignment(HorizontalAlignment::Center)
+            .vertical_alignment(VerticalAlignment::Middle)
+            .build();
+        for (index, label) in self.labels.iter().enumerate() {
+            // Only the active header shows focus
+            let mut header = flags.difference(StateFlags::FOCUSED | StateFlags::PRESSED);
+            if index == self.active {
+                header.set(StateFlags::SELECTED, true);
+                header.set(StateFlags::FOCUSED, flags.contains(StateFlags::FOCUSED));
+            }
+            let style = self.style.resolve(theme, header);
+            let bounds = self.header(index);
+            // Like buttons, leave room for shadows and the focus ring
+            Frame::themed(
+                bounds.offset(-(theme.spacing_xs() as i32)),
+                theme,
+                header,
+                style.background,
+                style.border,
+            )
+            .draw(target)?;
+            TextBox::with_textbox_style(
+                label.resolve(theme),
+                bounds.offset(-(style.padding as i32)),
+                MonoTextStyle::new(style.font, style.text),
+                textbox_style,
+            )
+            .draw(target)?;
+        }
+        Ok(())
+    }
+}
+
+impl<M, D, T, C, const N: usize> Element<M, D, T, C> for Tabs<'_, M, C, N>
+where
+    M: Copy + Clone,
+    D: DrawTarget<Color = C>,
+    C: PixelColor + Default + From<Rgb888>,
+    T: Theme<C>,
+{
+}
+
+impl<M, C, const N: usize> Transform for Tabs<'_, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn translate(&self, by: Point) -> Self {
+        let mut moved = self.clone();
+        moved.translate_mut(by);
+        moved
+    }
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.bounds.top_left += by;
+        self
+    }
+}
+
+impl<M, C, const N: usize> Place for Tabs<'_, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn place(&mut self, bounds: Rectangle) {
+        self.bounds = bounds;
+        self.state_manager.mark_dirty();
+    }
+}
+
+impl<M, C, const N: usize> Dimensions for Tabs<'_, M, C, N>
+where
+    M: Copy + Clone,
+    C: PixelColor,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::mock_display::MockDisplay;
+
+    use super::*;
+    use crate::themes::{ButtonKind, DefaultTheme, StateStyle};
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Message {
+        Tab(usize),
+    }
+
+    fn tabs() -> Tabs<'static, Message, Rgb888, 3> {
+        Tabs::new(
+            Rectangle::new(Point::zero(), Size::new(90, 60)),
+            ["A", "B", "C"],
+        )
+        .on_tab_changed(Message::Tab)
+    }
+
+    #[test]
+    fn keys_switch_only_while_focused() {
+        let mut tabs = tabs();
+        let right = InputEvent::Key(Key::Right);
+        assert!(tabs.handle_event(right).message().is_none());
+
+        tabs.get_state_manager_mut().set_focused(true);
+        assert_eq!(tabs.handle_event(right).message(), Some(Message::Tab(1)));
+        assert_eq!(tabs.handle_event(right).message(), Some(Message::Tab(2)));
+        // No wrapping past the last tab
+        assert!(tabs.handle_event(right).message().is_none());
+        let left = InputEvent::Key(Key::Left);
+        assert_eq!(tabs.handle_event(left).message(), Some(Message::Tab(1)));
+    }
+
+    #[test]
+    fn swipes_turn_pages() {
+        let mut tabs = tabs();
+        tabs.handle_event(InputEvent::Touch(Point::new(70, 40)));
+        let response = tabs.handle_event(InputEvent::TouchRelease(Point::new(20, 45)));
+        assert_eq!(response.message(), Some(Message::Tab(1)));
+
+        // Too short, or mostly vertical
+        tabs.handle_event(InputEvent::Touch(Point::new(50, 40)));
+        assert!(
+            tabs.handle_event(InputEvent::TouchRelease(Point::new(40, 40)))
+                .message()
+                .is_none()
+        );
+        tabs.handle_event(InputEvent::Touch(Point::new(50, 25)));
+        let response = tabs.handle_event(InputEvent::TouchRelease(Point::new(80, 59)));
+        assert!(response.message().is_none());
+        assert_eq!(tabs.active(), 1);
+    }
+
+    #[test]
+    fn active_header_looks_selected() {
+        let theme = DefaultTheme::<Rgb888>::new();
+        let mut tabs = tabs();
+        // The mock display is 64 pixels wide
+        tabs.place(Rectangle::new(Point::zero(), Size::new(60, 60)));
+        tabs.set_active(2);
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        display.set_allow_out_of_bounds_drawing(true);
+        tabs.draw_with_theme(&mut display, &theme).unwrap();
+
+        let selected = StateStyle::resolve(&theme, &ButtonKind, StateFlags::SELECTED);
+        assert_eq!(selected.background, theme.button_pressed_bg());
+        assert_eq!(
+            display.get_pixel(Point::new(50, 10)),
+            Some(selected.background)
+        );
+        assert_eq!(
+            display.get_pixel(Point::new(5, 10)),
+            Some(theme.button_normal_bg())
+        );
+        assert_eq!(
+            display.get_pixel(Point::new(30, 40)),
+            Some(theme.panel_background_color())
+        );
+    }
+}